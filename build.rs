@@ -0,0 +1,89 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn env(name: &str) -> Result<String, String> {
+    std::env::var(name).map_err(|e| format!("Expected {name} to be set for build script: {e}"))
+}
+
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(env("CARGO_MANIFEST_DIR").ok()?)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+/// Renders seconds since the UNIX epoch as an RFC 3339 UTC timestamp.
+///
+/// See: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn rfc3339(timestamp: u64) -> String {
+    let (days, seconds) = ((timestamp / 86400) as i64, timestamp % 86400);
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z",
+        hour = seconds / 3600,
+        minute = seconds % 3600 / 60,
+        second = seconds % 60
+    )
+}
+
+fn main() -> Result<(), String> {
+    // N.B.: Honor reproducible build timestamps when set.
+    // See: https://reproducible-builds.org/docs/source-date-epoch/
+    let build_timestamp = match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(epoch) => epoch
+            .parse()
+            .map_err(|e| format!("Expected SOURCE_DATE_EPOCH to be an integer: {e}"))?,
+        Err(_) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| format!("The system clock is set before the UNIX epoch: {e}"))?
+            .as_secs(),
+    };
+    println!(
+        "cargo:rustc-env=SCIE_PANTS_GIT_COMMIT={commit}",
+        commit = git_commit().unwrap_or_else(|| "unknown".to_string())
+    );
+    println!(
+        "cargo:rustc-env=SCIE_PANTS_BUILD_DATE={date}",
+        date = rfc3339(build_timestamp)
+    );
+    println!(
+        "cargo:rustc-env=SCIE_PANTS_TARGET={target}",
+        target = env("TARGET")?
+    );
+    // N.B.: The package crate sets these to the tags of the scie-jump and ptex binaries it packages
+    // scie-pants with.
+    for (tag_env_var, rustc_env_var) in [
+        ("SCIE_JUMP_TAG", "SCIE_PANTS_SCIE_JUMP_TAG"),
+        ("PTEX_TAG", "SCIE_PANTS_PTEX_TAG"),
+    ] {
+        println!(
+            "cargo:rustc-env={rustc_env_var}={tag}",
+            tag = std::env::var(tag_env_var).unwrap_or_else(|_| "unknown".to_string())
+        );
+        println!("cargo:rerun-if-env-changed={tag_env_var}");
+    }
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    Ok(())
+}
@@ -5,13 +5,15 @@ use std::env;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
+use log::info;
 use tempfile::TempDir;
 use termcolor::{Color, WriteColor};
 
 use crate::utils::build::fingerprint;
-use crate::utils::exe::{execute, execute_with_input, Platform, CURRENT_PLATFORM};
+use crate::utils::exe::{execute, execute_with_input, prepare_exe, Platform, CURRENT_PLATFORM};
 use crate::utils::fs::{
     copy, create_tempdir, ensure_directory, remove_dir, rename, softlink, touch, write_file,
 };
@@ -47,6 +49,27 @@ fn assert_stderr_output(command: &mut Command, expected_messages: Vec<&str>) ->
     output
 }
 
+fn assert_failed_stderr_output(command: &mut Command, expected_messages: Vec<&str>) -> Output {
+    info!("Executing {command:#?}");
+    let output = command
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to spawn command: {command:?}"))
+        .unwrap();
+    assert!(
+        !output.status.success(),
+        "Expected command to fail, but it succeeded: {command:?}"
+    );
+    let stderr = decode_output(output.stderr.clone()).unwrap();
+    for expected_message in expected_messages {
+        assert!(
+            stderr.contains(expected_message),
+            "STDERR did not contain '{expected_message}':\n{stderr}"
+        );
+    }
+    output
+}
+
 pub(crate) fn run_integration_tests(
     workspace_root: &Path,
     tools_pex_path: &Path,
@@ -111,6 +134,7 @@ pub(crate) fn run_integration_tests(
         let venv_dir = dev_cache_dir.join("venvs");
         let pants_2_14_1_venv_dir = venv_dir.join("pants-2.14.1");
 
+        test_invalid_pants_source(scie_pants_scie);
         test_pants_source_mode(
             scie_pants_scie,
             &clone_dir,
@@ -129,6 +153,29 @@ pub(crate) fn run_integration_tests(
         test_caching_issue_129(scie_pants_scie);
         test_custom_pants_toml_issue_153(scie_pants_scie);
         test_pants_native_client_perms_issue_182(scie_pants_scie);
+        test_pants_version_from_binary_name(scie_pants_scie);
+        test_launch_plan(scie_pants_scie);
+        test_hermetic_env(scie_pants_scie);
+        test_launch_plan_redaction(scie_pants_scie);
+        test_bug_report(scie_pants_scie);
+        test_exit_codes(scie_pants_scie);
+        test_json_error_format(scie_pants_scie);
+        test_pants_toml_diagnostics(scie_pants_scie);
+        test_pants_version_validation(scie_pants_scie);
+        test_pants_version_aliases(scie_pants_scie);
+        test_min_version(scie_pants_scie);
+        test_self_report_json(scie_pants_scie);
+        test_noninteractive(scie_pants_scie);
+        test_init(scie_pants_scie);
+        test_set_version(scie_pants_scie);
+        test_upgrade_pants(scie_pants_scie);
+        test_run_many(scie_pants_scie);
+        test_cache_list(scie_pants_scie);
+        test_cache_gc(scie_pants_scie);
+        test_cache_gc_reinstall(scie_pants_scie);
+        test_offline(scie_pants_scie);
+        test_prefetch(scie_pants_scie);
+        test_pants_source_debug(scie_pants_scie);
     }
 
     // Max Python supported is 3.8 and only Linux and macOS x86_64 wheels were released.
@@ -337,6 +384,8 @@ fn test_initialize_new_pants_project(scie_pants_scie: &Path) {
     execute_with_input(
         Command::new(scie_pants_scie)
             .arg("-V")
+            // N.B.: Piping answers to the prompts requires opting out of non-interactive mode.
+            .env("PANTS_BOOTSTRAP_NONINTERACTIVE", "0")
             .current_dir(project_subdir),
         "yes".as_bytes(),
     )
@@ -351,6 +400,8 @@ fn test_set_pants_version(scie_pants_scie: &Path) {
     execute_with_input(
         Command::new(scie_pants_scie)
             .arg("-V")
+            // N.B.: Piping answers to the prompts requires opting out of non-interactive mode.
+            .env("PANTS_BOOTSTRAP_NONINTERACTIVE", "0")
             .current_dir(existing_project_dir.path()),
         "Y".as_bytes(),
     )
@@ -405,6 +456,54 @@ fn test_dot_env_loading(scie_pants_scie: &Path, clone_root: &TempDir) {
     .unwrap();
 }
 
+fn test_invalid_pants_source(scie_pants_scie: &Path) {
+    integration_test!("Verify invalid PANTS_SOURCE values are reported clearly.");
+    let tmpdir = create_tempdir().unwrap();
+    let user_repo_dir = tmpdir.path().join("user-repo");
+    ensure_directory(&user_repo_dir, true).unwrap();
+    touch(&user_repo_dir.join("pants.toml")).unwrap();
+
+    let missing = tmpdir.path().join("missing");
+    let expected_message = format!(
+        "The Pants source directory {missing} (set via the PANTS_SOURCE env var) does not exist.",
+        missing = missing.display()
+    );
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_SOURCE", &missing)
+            .current_dir(&user_repo_dir),
+        vec![&expected_message],
+    );
+
+    let not_pants = tmpdir.path().join("not-pants");
+    ensure_directory(&not_pants, true).unwrap();
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_SOURCE", &not_pants)
+            .current_dir(&user_repo_dir),
+        vec!["does not look like a Pants checkout"],
+    );
+
+    touch(&user_repo_dir.join("pants")).unwrap();
+    touch(
+        &user_repo_dir
+            .join("src")
+            .join("python")
+            .join("pants")
+            .join("VERSION"),
+    )
+    .unwrap();
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_SOURCE", ".")
+            .current_dir(&user_repo_dir),
+        vec!["is the current build root"],
+    );
+}
+
 fn test_pants_source_mode(
     scie_pants_scie: &Path,
     clone_dir: &Path,
@@ -562,16 +661,107 @@ fn test_pants_from_sources_mode(
     softlink(scie_pants_scie, &pants_from_sources).unwrap();
 
     assert_stderr_output(
-        Command::new(pants_from_sources)
+        Command::new(&pants_from_sources)
             .arg("-V")
             .env("SCIE_PANTS_TEST_MODE", "pants_from_sources mode")
             .env("PANTS_VENV_DIR_PREFIX", pants_2_14_1_venv_dir)
-            .current_dir(user_repo_dir),
+            .current_dir(&user_repo_dir),
         vec![
             "The pants_from_sources mode is working.",
             "Pants from sources argv: --no-verify-config -V.",
         ],
     );
+
+    integration_test!("Verify pants_from_sources mode with a configured pants_source.");
+    let elsewhere_dir = side_by_side_root.path().join("oss").join("pants");
+    ensure_directory(elsewhere_dir.parent().unwrap(), true).unwrap();
+    softlink(pants_2_14_1_clone_dir, &elsewhere_dir).unwrap();
+    write_file(
+        &user_repo_dir.join("pants.toml"),
+        false,
+        r#"
+        [scie-pants]
+        pants_source = "../oss/pants"
+        "#,
+    )
+    .unwrap();
+    let user_repo_subdir = user_repo_dir.join("subdir");
+    ensure_directory(&user_repo_subdir, true).unwrap();
+    // Ensure the default ../pants location is not what gets used.
+    std::fs::remove_file(&pants_dir).unwrap();
+    assert_stderr_output(
+        Command::new(&pants_from_sources)
+            .arg("-V")
+            .env("SCIE_PANTS_TEST_MODE", "configured pants_source mode")
+            .env("PANTS_VENV_DIR_PREFIX", pants_2_14_1_venv_dir)
+            .current_dir(&user_repo_subdir),
+        vec![
+            "The configured pants_source mode is working.",
+            "Pants from sources argv: --no-verify-config -V.",
+        ],
+    );
+
+    integration_test!("Verify pants_from_sources mode checks the pinned Pants version.");
+    write_file(
+        &user_repo_dir.join("pants.toml"),
+        false,
+        r#"
+        [GLOBAL]
+        pants_version = "2.16.0"
+        [scie-pants]
+        pants_source = "../oss/pants"
+        "#,
+    )
+    .unwrap();
+    let expected_message = "is at version 2.14.1+Custom-Local, but";
+    assert_stderr_output(
+        Command::new(&pants_from_sources)
+            .arg("-V")
+            .env("PANTS_VENV_DIR_PREFIX", pants_2_14_1_venv_dir)
+            .current_dir(&user_repo_subdir),
+        vec![
+            expected_message,
+            "Set PANTS_BOOTSTRAP_SOURCE_STRICT_VERSION=1 to make this an error.",
+        ],
+    );
+    assert_failed_stderr_output(
+        Command::new(&pants_from_sources)
+            .arg("-V")
+            .env("PANTS_BOOTSTRAP_SOURCE_STRICT_VERSION", "1")
+            .env("PANTS_VENV_DIR_PREFIX", pants_2_14_1_venv_dir)
+            .current_dir(&user_repo_subdir),
+        vec![expected_message],
+    );
+
+    integration_test!(
+        "Verify pants_from_sources mode sources .pants.bootstrap and uses configured args."
+    );
+    write_file(
+        &user_repo_dir.join("pants.toml"),
+        false,
+        r#"
+        [scie-pants]
+        pants_source = "../oss/pants"
+        pants_source_args = ["--no-verify-config", "--no-pantsd"]
+        "#,
+    )
+    .unwrap();
+    write_file(
+        &user_repo_dir.join(".pants.bootstrap"),
+        false,
+        r#"export SCIE_PANTS_TEST_MODE=".pants.bootstrap sourcing""#,
+    )
+    .unwrap();
+    assert_stderr_output(
+        Command::new(&pants_from_sources)
+            .arg("-V")
+            .env("PANTS_VENV_DIR_PREFIX", pants_2_14_1_venv_dir)
+            .current_dir(&user_repo_subdir),
+        vec![
+            "The .pants.bootstrap sourcing is working.",
+            "Pants from sources argv: --no-verify-config --no-pantsd -V.",
+        ],
+    );
 }
 
 fn test_delegate_pants_in_pants_repo(scie_pants_scie: &Path, pants_2_14_1_clone_dir: &PathBuf) {
@@ -854,3 +1044,1202 @@ fn test_pants_native_client_perms_issue_182(scie_pants_scie: &Path) {
         decode_output(output.unwrap().stdout).unwrap().trim()
     );
 }
+
+fn test_pants_version_from_binary_name(scie_pants_scie: &Path) {
+    integration_test!("Verifying a pants-<version> binary name selects the Pants version");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml_content = r#"
+        [GLOBAL]
+        pants_version = "2.17.0a1"
+        "#;
+    write_file(&tmpdir.path().join("pants.toml"), false, pants_toml_content).unwrap();
+
+    let assert_pants_version = |binary_name: &str, expected_version: &str| {
+        let binary = tmpdir.path().join(binary_name);
+        softlink(scie_pants_scie, &binary).unwrap();
+        let output = execute(
+            Command::new(&binary)
+                .arg("-V")
+                .current_dir(&tmpdir)
+                .stdout(Stdio::piped()),
+        );
+        assert_eq!(
+            expected_version,
+            decode_output(output.unwrap().stdout).unwrap().trim()
+        );
+    };
+    assert_pants_version("pants-2.17.0.dev4", "2.17.0.dev4");
+    assert_pants_version("pants@2.15.0rc5", "2.15.0rc5");
+    assert_pants_version("pants-latest-and-greatest", "2.17.0a1");
+    assert_pants_version("pants-2.17.x", "2.17.0a1");
+    assert_pants_version("pants-3", "2.17.0a1");
+}
+
+fn test_launch_plan(scie_pants_scie: &Path) {
+    integration_test!("Verifying PANTS_BOOTSTRAP_LAUNCH_PLAN=json reports env removals");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml_content = r#"
+        [GLOBAL]
+        pants_version = "2.17.0a1"
+        "#;
+    write_file(&tmpdir.path().join("pants.toml"), false, pants_toml_content).unwrap();
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("PANTS_BOOTSTRAP_LAUNCH_PLAN", "json")
+            .env("PEX_ROOT", tmpdir.path().join("stale-pex-root"))
+            .current_dir(&tmpdir)
+            .stdout(Stdio::piped()),
+    );
+    let stdout = decode_output(output.unwrap().stdout).unwrap();
+    for expected in [
+        r#""op": "unset""#,
+        r#""name": "PEX_ROOT""#,
+        r#""name": "VIRTUAL_ENV""#,
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "Launch plan did not contain '{expected}':\n{stdout}"
+        );
+    }
+}
+
+fn test_hermetic_env(scie_pants_scie: &Path) {
+    integration_test!("Verifying hermetic env mode only passes allowlisted env vars to Pants");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml_content = r#"
+        [GLOBAL]
+        pants_version = "2.17.0a1"
+        [scie-pants]
+        hermetic_env = true
+        hermetic_env_allowlist = ["ALLOWED_*"]
+        "#;
+    write_file(&tmpdir.path().join("pants.toml"), false, pants_toml_content).unwrap();
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("PANTS_BOOTSTRAP_LAUNCH_PLAN", "json")
+            .env("ALLOWED_VAR", "1")
+            .env("LEAKED_VAR", "1")
+            .env("XDG_CACHE_HOME", tmpdir.path().join("cache"))
+            .current_dir(&tmpdir)
+            .stdout(Stdio::piped()),
+    );
+    let stdout = decode_output(output.unwrap().stdout).unwrap();
+    assert!(
+        stdout.contains(r#""name": "LEAKED_VAR""#),
+        "Expected LEAKED_VAR to be unset:\n{stdout}"
+    );
+    assert!(
+        !stdout.contains(r#""name": "XDG_CACHE_HOME""#),
+        "Expected XDG_CACHE_HOME to be retained:\n{stdout}"
+    );
+    assert!(
+        !stdout.contains(r#""name": "ALLOWED_VAR""#),
+        "Expected ALLOWED_VAR to be retained:\n{stdout}"
+    );
+
+    // An explicit opt-out via env var trumps the config.
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("PANTS_BOOTSTRAP_LAUNCH_PLAN", "json")
+            .env("PANTS_BOOTSTRAP_HERMETIC_ENV", "0")
+            .env("LEAKED_VAR", "1")
+            .current_dir(&tmpdir)
+            .stdout(Stdio::piped()),
+    );
+    let stdout = decode_output(output.unwrap().stdout).unwrap();
+    assert!(
+        !stdout.contains(r#""name": "LEAKED_VAR""#),
+        "Expected LEAKED_VAR to be retained:\n{stdout}"
+    );
+}
+
+fn test_launch_plan_redaction(scie_pants_scie: &Path) {
+    integration_test!("Verifying configured env var patterns are redacted in the launch plan");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml_content = r#"
+        [GLOBAL]
+        pants_version = "2.17.0a1"
+        [scie-pants]
+        redact_env_patterns = ["*_bin_name"]
+        "#;
+    write_file(&tmpdir.path().join("pants.toml"), false, pants_toml_content).unwrap();
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("PANTS_BOOTSTRAP_LAUNCH_PLAN", "json")
+            .env("PANTS_BIN_NAME", "super-secret-pants")
+            .current_dir(&tmpdir)
+            .stdout(Stdio::piped()),
+    );
+    let stdout = decode_output(output.unwrap().stdout).unwrap();
+    assert!(
+        stdout.contains("<redacted>"),
+        "Expected PANTS_BIN_NAME to be redacted:\n{stdout}"
+    );
+    assert!(
+        !stdout.contains("super-secret-pants"),
+        "Expected PANTS_BIN_NAME to be redacted:\n{stdout}"
+    );
+}
+
+fn test_bug_report(scie_pants_scie: &Path) {
+    integration_test!("Verifying SCIE_BOOT=bug-report writes a bug report tarball");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml_content = r#"
+        [GLOBAL]
+        pants_version = "2.17.0a1"
+        "#;
+    write_file(&tmpdir.path().join("pants.toml"), false, pants_toml_content).unwrap();
+
+    let bug_report = tmpdir.path().join("bug-report.tar.gz");
+    let scie_base = tmpdir.path().join("nce");
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("SCIE_BASE", &scie_base)
+            .env("SCIE_BOOT", "bug-report")
+            .arg("--output")
+            .arg(&bug_report)
+            .current_dir(&tmpdir)
+            .stdout(Stdio::piped()),
+    );
+    let stdout = decode_output(output.unwrap().stdout).unwrap();
+    assert!(
+        stdout.contains(&format!("Wrote bug report to {}", bug_report.display())),
+        "Expected the bug report path to be printed:\n{stdout}"
+    );
+
+    let output = execute(
+        Command::new("tar")
+            .arg("-tzf")
+            .arg(&bug_report)
+            .stdout(Stdio::piped()),
+    );
+    let entries = decode_output(output.unwrap().stdout).unwrap();
+    for expected_entry in [
+        "scie-pants-bug-report/report.json",
+        "scie-pants-bug-report/lift.json",
+        "scie-pants-bug-report/launch-plan.json",
+        "scie-pants-bug-report/pants.toml",
+    ] {
+        assert!(
+            entries.contains(expected_entry),
+            "Expected the bug report to contain {expected_entry}:\n{entries}"
+        );
+    }
+
+    // Gathering the launch plan should not count as a use of Pants 2.17.0a1.
+    let usage_logs = walkdir::WalkDir::new(&scie_base)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_name() == "pants-usage.json")
+        .map(|entry| entry.into_path())
+        .collect::<Vec<_>>();
+    assert!(
+        usage_logs.is_empty(),
+        "Expected no Pants usage to be recorded: {usage_logs:?}"
+    );
+}
+
+fn test_exit_codes(scie_pants_scie: &Path) {
+    integration_test!("Verifying launcher failures exit with distinct exit codes");
+
+    let tmpdir = create_tempdir().unwrap();
+
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .env("PANTS_SOURCE", tmpdir.path())
+            .current_dir(&tmpdir),
+        vec!["Failed to find pants.toml, BUILDROOT or BUILD_ROOT"],
+    );
+    assert_eq!(Some(66), output.status.code());
+
+    write_file(&tmpdir.path().join("pants.toml"), false, "[GLOBAL").unwrap();
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie).current_dir(&tmpdir),
+        vec!["Failed to parse Pants config"],
+    );
+    assert_eq!(Some(78), output.status.code());
+
+    let pants_toml_content = r#"
+        [GLOBAL]
+        pants_version = "2.17.0a1"
+        "#;
+    write_file(&tmpdir.path().join("pants.toml"), false, pants_toml_content).unwrap();
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .env("PANTS_SHA", "8e381dbf90cae57c5da2b223c577b36ca86cace9")
+            .env("PANTS_VERSION", "2.17.0a1")
+            .current_dir(&tmpdir),
+        vec!["Please choose one."],
+    );
+    assert_eq!(Some(64), output.status.code());
+
+    for pants_bootstrap in ["false", "exit 3"] {
+        write_file(
+            &tmpdir.path().join(".pants.bootstrap"),
+            false,
+            pants_bootstrap,
+        )
+        .unwrap();
+        let output = assert_failed_stderr_output(
+            Command::new(scie_pants_scie).arg("-V").current_dir(&tmpdir),
+            vec![],
+        );
+        assert_eq!(
+            Some(69),
+            output.status.code(),
+            "Expected a .pants.bootstrap of `{pants_bootstrap}` to exit 69."
+        );
+    }
+}
+
+fn test_json_error_format(scie_pants_scie: &Path) {
+    integration_test!("Verifying SCIE_PANTS_ERROR_FORMAT=json reports errors as JSON");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml = tmpdir.path().join("pants.toml");
+    write_file(&pants_toml, false, "[GLOBAL]\npants_version = 1\n").unwrap();
+
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .env("SCIE_PANTS_ERROR_FORMAT", "json")
+            .current_dir(&tmpdir),
+        vec![
+            r#""code":"bad_config""#,
+            r#""exit_code":78"#,
+            r#""line":2"#,
+            r#""column":17"#,
+        ],
+    );
+    assert_eq!(Some(78), output.status.code());
+    let stderr = decode_output(output.stderr).unwrap();
+    assert!(
+        stderr.trim().starts_with('{') && stderr.trim().ends_with('}'),
+        "Expected a single JSON object on STDERR:\n{stderr}"
+    );
+}
+
+fn test_pants_toml_diagnostics(scie_pants_scie: &Path) {
+    integration_test!("Verifying pants.toml errors are reported with rustc-style diagnostics");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml = tmpdir.path().join("pants.toml");
+    let pants_toml_content = r#"[GLOBAL]
+pants_version = "2.17.0a1"
+
+[DEFAULT]
+delegate_bootstrap = "yes"
+"#;
+    write_file(&pants_toml, false, pants_toml_content).unwrap();
+
+    let location = format!("--> {pants_toml}:5:22", pants_toml = pants_toml.display());
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie).current_dir(&tmpdir),
+        vec![
+            r#"error: invalid type: string "yes", expected a boolean"#,
+            &location,
+            r#"5 | delegate_bootstrap = "yes""#,
+            "|                      ^^^^^",
+            "= help: `[DEFAULT] delegate_bootstrap` should be a boolean",
+        ],
+    );
+}
+
+fn test_pants_version_validation(scie_pants_scie: &Path) {
+    integration_test!("Verifying Pants versions and SHAs are validated before bootstrapping");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml = tmpdir.path().join("pants.toml");
+
+    write_file(
+        &pants_toml,
+        false,
+        "[GLOBAL]\npants_version = \"v2.17.0\"\n",
+    )
+    .unwrap();
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie).current_dir(&tmpdir),
+        vec!["leading 'v' not allowed in v2.17.0; did you mean 2.17.0?"],
+    );
+
+    write_file(
+        &pants_toml,
+        false,
+        "[GLOBAL]\npants_version = \"2.17.0a1\"\n",
+    )
+    .unwrap();
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .env("PANTS_VERSION", "2.17.0~rc1")
+            .current_dir(&tmpdir),
+        vec!["Invalid PANTS_VERSION: 2.17.0~rc1 is not a valid PEP 440 version"],
+    );
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .env("PANTS_SHA", "8e381dbf")
+            .current_dir(&tmpdir),
+        vec!["Invalid PANTS_SHA: 8e381dbf is 8 characters long"],
+    );
+}
+
+fn test_pants_version_aliases(scie_pants_scie: &Path) {
+    integration_test!("Verifying Pants version aliases resolve to concrete Pants releases");
+
+    let tmpdir = create_tempdir().unwrap();
+    write_file(
+        &tmpdir.path().join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"2.16\"\n",
+    )
+    .unwrap();
+    let output = assert_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("RUST_LOG", "info")
+            .stdout(Stdio::piped())
+            .current_dir(&tmpdir),
+        vec!["Resolved Pants version 2.16 to 2.16."],
+    );
+    let stdout = decode_output(output.stdout).unwrap();
+    assert!(
+        stdout.trim().starts_with("2.16."),
+        "Expected a 2.16 Pants release, got: {stdout}"
+    );
+
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .env("PANTS_VERSION", "1.99")
+            .current_dir(&tmpdir),
+        vec!["There is no Pants release matching the Pants version 1.99."],
+    );
+
+    integration_test!("Verifying Pants version aliases resolve visibly when PyPI is unreachable");
+    let scie_base = tmpdir.path().join("nce");
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .env("SCIE_BASE", &scie_base)
+            .current_dir(&tmpdir),
+        vec![
+            "Cannot resolve the Pants version 2.16 since the current Pants releases could not be \
+            determined and scie-pants knows of no stable release matching it",
+        ],
+    );
+    assert_eq!(Some(68), output.status.code());
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_VERSION", "latest-stable")
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .env("SCIE_BASE", &scie_base)
+            .current_dir(&tmpdir),
+        vec![
+            "WARNING: Resolved Pants version latest-stable to ",
+            " using the Pants releases known when this scie-pants was built since the current \
+            Pants releases could not be determined: PANTS_BOOTSTRAP_OFFLINE is set.",
+        ],
+    );
+}
+
+fn test_min_version(scie_pants_scie: &Path) {
+    integration_test!("Verifying the scie-pants min_version required by a repo is enforced");
+
+    let tmpdir = create_tempdir().unwrap();
+    write_file(
+        &tmpdir.path().join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"2.16.0\"\n\n[scie-pants]\nmin_version = \"999.0.0\"\n",
+    )
+    .unwrap();
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie).arg("-V").current_dir(&tmpdir),
+        vec![
+            "this repo requires scie-pants >= 999.0.0, run `SCIE_BOOT=update scie-pants`",
+            "min_version = \"999.0.0\"",
+        ],
+    );
+    assert_eq!(Some(75), output.status.code());
+
+    let output = assert_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_BOOTSTRAP_SKIP_MIN_VERSION_CHECK", "1")
+            .stdout(Stdio::piped())
+            .current_dir(&tmpdir),
+        vec![
+            "WARNING: This repo requires scie-pants >= 999.0.0 but this is scie-pants ",
+            "Proceeding since PANTS_BOOTSTRAP_SKIP_MIN_VERSION_CHECK is set.",
+        ],
+    );
+    assert_eq!("2.16.0", decode_output(output.stdout).unwrap().trim());
+}
+
+fn test_self_report_json(scie_pants_scie: &Path) {
+    integration_test!(
+        "Verifying PANTS_BOOTSTRAP_VERSION=report-json describes the scie-pants build"
+    );
+
+    let report = |mode: &str| {
+        let output = execute(
+            Command::new(scie_pants_scie)
+                .env("PANTS_BOOTSTRAP_VERSION", mode)
+                .stdout(Stdio::piped()),
+        )
+        .unwrap();
+        decode_output(output.stdout).unwrap()
+    };
+    let version = report("report");
+    let report = report("report-json");
+    for expected in [
+        format!(r#""version": "{version}""#, version = version.trim()),
+        r#""tools_pex_hash": ""#.to_string(),
+        r#""name": "cpython-3.9."#.to_string(),
+        r#""errors": []"#.to_string(),
+    ] {
+        assert!(
+            report.contains(&expected),
+            "The self-report did not contain '{expected}':\n{report}"
+        );
+    }
+    for unexpected in [r#""scie_jump_tag": "unknown""#, r#""ptex_tag": "unknown""#] {
+        assert!(
+            !report.contains(unexpected),
+            "The self-report unexpectedly contained '{unexpected}':\n{report}"
+        );
+    }
+}
+
+fn test_noninteractive(scie_pants_scie: &Path) {
+    integration_test!("Verifying scie-pants fails fast instead of prompting when non-interactive");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml = tmpdir.path().join("pants.toml");
+    touch(&pants_toml).unwrap();
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_BOOTSTRAP_NONINTERACTIVE", "1")
+            .current_dir(&tmpdir),
+        vec![
+            "No Pants version is configured and scie-pants will not prompt for one since \
+            PANTS_BOOTSTRAP_NONINTERACTIVE is set.",
+            &format!(
+                "Set `pants_version` in the `[GLOBAL]` table of {pants_toml}",
+                pants_toml = pants_toml.display()
+            ),
+        ],
+    );
+    assert_eq!(Some(78), output.status.code());
+
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env_remove("PANTS_BOOTSTRAP_NONINTERACTIVE")
+            .env("CI", "true")
+            .current_dir(create_tempdir().unwrap().path()),
+        vec![
+            "No Pants build root was found and scie-pants will not prompt to create one since the \
+            CI env var is set.",
+        ],
+    );
+    assert_eq!(Some(66), output.status.code());
+
+    // N.B.: Like the other boolean env vars, CI=false is off; but stdin is not a terminal here.
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env_remove("PANTS_BOOTSTRAP_NONINTERACTIVE")
+            .env("CI", "false")
+            .stdin(Stdio::null())
+            .current_dir(create_tempdir().unwrap().path()),
+        vec![
+            "No Pants build root was found and scie-pants will not prompt to create one since \
+            stdin is not a terminal.",
+        ],
+    );
+}
+
+fn test_init(scie_pants_scie: &Path) {
+    integration_test!("Verifying `SCIE_BOOT=init scie-pants` scaffolds a new Pants project");
+
+    let tmpdir = create_tempdir().unwrap();
+    let project_dir = tmpdir.path().join("project");
+    let init = || {
+        let mut command = Command::new(scie_pants_scie);
+        command.env("SCIE_BOOT", "init").args([
+            "--dir",
+            project_dir.to_str().unwrap(),
+            "--pants-version",
+            "2.16.0",
+            "--backend",
+            "python",
+            "--interpreter-constraint",
+            "CPython==3.9.*",
+            "--build-root-marker",
+            "--gitignore",
+        ]);
+        command
+    };
+    execute(&mut init()).unwrap();
+    let pants_toml = std::fs::read_to_string(project_dir.join("pants.toml")).unwrap();
+    for expected in [
+        r#"pants_version = "2.16.0""#,
+        r#""pants.backend.python","#,
+        r#"interpreter_constraints = ["CPython==3.9.*"]"#,
+    ] {
+        assert!(
+            pants_toml.contains(expected),
+            "The pants.toml did not contain '{expected}':\n{pants_toml}"
+        );
+    }
+    assert!(project_dir.join("BUILD_ROOT").is_file());
+    let gitignore = std::fs::read_to_string(project_dir.join(".gitignore")).unwrap();
+    assert!(gitignore.lines().any(|line| line == ".pants.d/"));
+
+    assert_failed_stderr_output(
+        &mut init(),
+        vec!["Refusing to overwrite", "Re-run with --force to overwrite."],
+    );
+    execute(init().arg("--force")).unwrap();
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .stdout(Stdio::piped())
+            .current_dir(&project_dir),
+    )
+    .unwrap();
+    assert_eq!("2.16.0", decode_output(output.stdout).unwrap().trim());
+}
+
+fn test_set_version(scie_pants_scie: &Path) {
+    integration_test!(
+        "Verifying `SCIE_BOOT=set-version scie-pants` preserves pants.toml formatting"
+    );
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml = tmpdir.path().join("pants.toml");
+    write_file(
+        &pants_toml,
+        false,
+        "# Managed by the build team.\n\
+        [GLOBAL]\n\
+        pants_version = '2.16.0'  # Bump with set-version.\n\
+        backend_packages = []\n",
+    )
+    .unwrap();
+    let set_version = |pants_version: &str| {
+        let mut command = Command::new(scie_pants_scie);
+        command
+            .env("SCIE_BOOT", "set-version")
+            .arg(pants_version)
+            .current_dir(&tmpdir);
+        command
+    };
+
+    execute(set_version("2.16.0").arg("--check")).unwrap();
+    assert_failed_stderr_output(
+        set_version("2.17.0").arg("--check"),
+        vec!["would change from 2.16.0 to 2.17.0"],
+    );
+    execute(&mut set_version("2.17.0")).unwrap();
+    assert_eq!(
+        "# Managed by the build team.\n\
+        [GLOBAL]\n\
+        pants_version = \"2.17.0\"  # Bump with set-version.\n\
+        backend_packages = []\n",
+        std::fs::read_to_string(&pants_toml).unwrap()
+    );
+    execute(set_version("2.17.0").arg("--check")).unwrap();
+}
+
+fn test_upgrade_pants(scie_pants_scie: &Path) {
+    integration_test!("Verifying `SCIE_BOOT=upgrade-pants scie-pants` upgrades the Pants version");
+
+    let tmpdir = create_tempdir().unwrap();
+    let pants_toml = tmpdir.path().join("pants.toml");
+    write_file(&pants_toml, false, "[GLOBAL]\npants_version = \"2.15.0\"\n").unwrap();
+    let releases = tmpdir.path().join("releases.json");
+    write_file(
+        &releases,
+        false,
+        r#"{"releases": {"2.15.0": [{}], "2.15.1": [{}], "2.16.0": [{}], "2.16.1rc0": [{}]}}"#,
+    )
+    .unwrap();
+    let upgrade_pants = |to: &str| {
+        let mut command = Command::new(scie_pants_scie);
+        command
+            .env("SCIE_BOOT", "upgrade-pants")
+            .args(["--to", to, "--releases-file", releases.to_str().unwrap()])
+            .stdout(Stdio::piped())
+            .current_dir(&tmpdir);
+        command
+    };
+
+    let output = execute(upgrade_pants("next-minor").arg("--dry-run")).unwrap();
+    let stdout = decode_output(output.stdout).unwrap();
+    assert!(
+        stdout.contains("-pants_version = \"2.15.0\"\n+pants_version = \"2.16.0\"\n"),
+        "Expected a diff upgrading to 2.16.0:\n{stdout}"
+    );
+    assert!(stdout.contains("src/python/pants/notes/2.16.x.md"));
+    assert_eq!(
+        "[GLOBAL]\npants_version = \"2.15.0\"\n",
+        std::fs::read_to_string(&pants_toml).unwrap()
+    );
+
+    execute(&mut upgrade_pants("latest-patch")).unwrap();
+    assert_eq!(
+        "[GLOBAL]\npants_version = \"2.15.1\"\n",
+        std::fs::read_to_string(&pants_toml).unwrap()
+    );
+
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .env("SCIE_BOOT", "upgrade-pants")
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .current_dir(&tmpdir),
+        vec![
+            "Failed to determine the current Pants releases. If you are offline, pass \
+            --releases-file with a saved copy of https://pypi.org/pypi/pantsbuild.pants/json.",
+        ],
+    );
+    assert_eq!(
+        "[GLOBAL]\npants_version = \"2.15.1\"\n",
+        std::fs::read_to_string(&pants_toml).unwrap()
+    );
+}
+
+fn test_run_many(scie_pants_scie: &Path) {
+    integration_test!("Verifying `SCIE_BOOT=run-many scie-pants` runs Pants in each build root");
+
+    let tmpdir = create_tempdir().unwrap();
+    for (repo, pants_version) in [("repo-a", "2.15.0"), ("repo-b", "2.16.0")] {
+        let repo_dir = tmpdir.path().join(repo);
+        ensure_directory(&repo_dir, false).unwrap();
+        write_file(
+            &repo_dir.join("pants.toml"),
+            false,
+            format!("[GLOBAL]\npants_version = \"{pants_version}\"\n"),
+        )
+        .unwrap();
+    }
+    ensure_directory(&tmpdir.path().join("not-a-repo"), false).unwrap();
+
+    let output = Command::new(scie_pants_scie)
+        .env("SCIE_BOOT", "run-many")
+        .args(["--glob", "repo-*", "--jobs", "2", "--", "-V"])
+        .stdout(Stdio::piped())
+        .current_dir(&tmpdir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = decode_output(output.stdout).unwrap();
+    for expected in ["==> repo-a <==\n2.15.0\n", "==> repo-b <==\n2.16.0\n"] {
+        assert!(
+            stdout.contains(expected),
+            "The run-many output did not contain '{expected}':\n{stdout}"
+        );
+    }
+    assert!(stdout.contains("repo-a      2.15.0         0\n"));
+
+    // N.B.: An invalid PANTS_SHA makes each Pants launch fail with an error on stderr.
+    let output = Command::new(scie_pants_scie)
+        .env("SCIE_BOOT", "run-many")
+        .env("PANTS_SHA", "bogus")
+        .args(["--glob", "repo-*", "--jobs", "2", "--", "-V"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(&tmpdir)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = decode_output(output.stdout).unwrap();
+    let stderr = decode_output(output.stderr).unwrap();
+    assert!(stdout.contains("==> repo-a <==\n"), "{stdout}");
+    assert!(!stdout.contains("Invalid PANTS_SHA"), "{stdout}");
+    assert_eq!(2, stderr.matches("Invalid PANTS_SHA").count(), "{stderr}");
+}
+
+fn test_cache_list(scie_pants_scie: &Path) {
+    integration_test!("Verifying `SCIE_BOOT=cache scie-pants list` lists the Pants installs");
+
+    let tmpdir = create_tempdir().unwrap();
+    write_file(
+        &tmpdir.path().join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"2.16.0\"\n",
+    )
+    .unwrap();
+    execute(Command::new(scie_pants_scie).arg("-V").current_dir(&tmpdir)).unwrap();
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("SCIE_BOOT", "cache")
+            .args(["list", "--json"])
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    let listing = decode_output(output.stdout).unwrap();
+    for expected in [
+        r#""name": "2.16.0""#,
+        r#""name": "pex_root""#,
+        r#""name": "cpython-"#,
+        r#""errors": []"#,
+    ] {
+        assert!(
+            listing.contains(expected),
+            "The cache listing did not contain '{expected}':\n{listing}"
+        );
+    }
+    let venv = listing
+        .split_once(r#""name": "2.16.0""#)
+        .and_then(|(_, rest)| rest.split_once('}'))
+        .map(|(venv, _)| venv)
+        .unwrap();
+    assert!(
+        !venv.contains(r#""last_used": null"#),
+        "Expected the use of Pants 2.16.0 to be recorded:\n{listing}"
+    );
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("SCIE_BOOT", "cache")
+            .arg("list")
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    let listing = decode_output(output.stdout).unwrap();
+    assert!(listing.contains("Pants version"), "{listing}");
+    assert!(listing.contains("\n  2.16.0 "), "{listing}");
+}
+
+fn test_cache_gc(scie_pants_scie: &Path) {
+    integration_test!(
+        "Verifying `SCIE_BOOT=cache scie-pants gc` only deletes unused Pants installs that are not \
+        pinned by a recently used build root"
+    );
+
+    let tmpdir = create_tempdir().unwrap();
+    let scie_base = tmpdir.path().join("nce");
+    let cache = |args: &[&str]| {
+        let output = execute(
+            Command::new(scie_pants_scie)
+                .env("SCIE_BASE", &scie_base)
+                .env("SCIE_BOOT", "cache")
+                .args(args)
+                .stdout(Stdio::piped()),
+        )
+        .unwrap();
+        decode_output(output.stdout).unwrap()
+    };
+    let listing = cache(&["list", "--json"]);
+    let bindings_dir = listing
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(r#""bindings_dir": ""#))
+        .and_then(|bindings_dir| bindings_dir.strip_suffix("\","))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| panic!("Expected a bindings_dir in:\n{listing}"));
+
+    let unused = bindings_dir.join("venvs").join("1.0.0");
+    let pinned = bindings_dir.join("venvs").join("1.0.1");
+    let age = |venv: &Path| {
+        touch(&venv.join("pyvenv.cfg")).unwrap();
+        // N.B.: Age the venv as if it was installed and last used long ago.
+        execute(Command::new("touch").args(["-t", "202001010000"]).arg(venv)).unwrap();
+    };
+    age(&unused);
+    age(&pinned);
+    // N.B.: Fake the lock and recorded output scie-jump keeps for the install binding of the unused
+    // venv.
+    let locks_dir = bindings_dir.parent().unwrap().join("locks");
+    let install_lock = locks_dir.join("install-1.0.0.lck");
+    let install_output = locks_dir.join("install-1.0.0");
+    touch(&install_lock).unwrap();
+    write_file(
+        &install_output,
+        false,
+        format!("VIRTUAL_ENV={unused}\n", unused = unused.display()),
+    )
+    .unwrap();
+    let build_root = tmpdir.path().join("repo");
+    write_file(
+        &build_root.join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"1.0.1\"\n",
+    )
+    .unwrap();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    // N.B.: 1577836800 is 2020-01-01T00:00:00Z.
+    let stale_build_root = tmpdir.path().join("stale-repo");
+    let usage_log = bindings_dir.join("pants-usage.json");
+    write_file(
+        &usage_log,
+        false,
+        format!(
+            r#"{{
+                "pants_versions": {{"1.0.0": 1577836800}},
+                "build_roots": {{
+                    "{build_root}": {{"pants_version": "0.9.0", "last_used": {now}}},
+                    "{stale_build_root}": {{"pants_version": "1.0.0", "last_used": 1577836800}}
+                }}
+            }}"#,
+            build_root = build_root.display(),
+            stale_build_root = stale_build_root.display()
+        ),
+    )
+    .unwrap();
+
+    let output = cache(&["gc", "--dry-run"]);
+    assert!(output.contains("Would delete Pants 1.0.0 "), "{output}");
+    assert!(
+        output.contains(&format!(
+            "since it is pinned by {build_root}.",
+            build_root = build_root.display()
+        )),
+        "{output}"
+    );
+    assert!(unused.exists());
+
+    assert!(install_output.exists());
+
+    let output = cache(&["gc"]);
+    assert!(output.contains("Deleted Pants 1.0.0 "), "{output}");
+    assert!(output.contains("Keeping Pants 1.0.1 "), "{output}");
+    assert!(!unused.exists());
+    assert!(pinned.exists());
+    assert!(!install_output.exists());
+
+    let usage = std::fs::read_to_string(&usage_log).unwrap();
+    assert!(
+        usage.contains(&format!(
+            "\"{build_root}\"",
+            build_root = build_root.display()
+        )),
+        "{usage}"
+    );
+    assert!(
+        !usage.contains(&format!(
+            "\"{stale_build_root}\"",
+            stale_build_root = stale_build_root.display()
+        )),
+        "{usage}"
+    );
+    assert!(!usage.contains("\"1.0.0\""), "{usage}");
+
+    integration_test!("Verifying `SCIE_BOOT=cache scie-pants gc` refuses to run during an install");
+    age(&unused);
+    let mut lock = fd_lock::RwLock::new(std::fs::File::create(&install_lock).unwrap());
+    let _install_in_progress = lock.write().unwrap();
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .env("SCIE_BASE", &scie_base)
+            .env("SCIE_BOOT", "cache")
+            .arg("gc"),
+        vec![&format!(
+            "The scie-jump binding lock {install_lock} is held; a Pants install may be in \
+            progress. Try again once it completes.",
+            install_lock = install_lock.display()
+        )],
+    );
+    assert!(unused.exists());
+}
+
+fn test_cache_gc_reinstall(scie_pants_scie: &Path) {
+    integration_test!(
+        "Verifying a Pants version deleted by `SCIE_BOOT=cache scie-pants gc` is re-installed"
+    );
+
+    let tmpdir = create_tempdir().unwrap();
+    let scie_base = tmpdir.path().join("nce");
+    let build_root = tmpdir.path().join("repo");
+    write_file(
+        &build_root.join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"2.16.0\"\n",
+    )
+    .unwrap();
+    let pants_version = || {
+        let output = execute(
+            Command::new(scie_pants_scie)
+                .arg("-V")
+                .env("SCIE_BASE", &scie_base)
+                .current_dir(&build_root)
+                .stdout(Stdio::piped()),
+        )
+        .unwrap();
+        decode_output(output.stdout).unwrap().trim().to_string()
+    };
+    assert_eq!("2.16.0", pants_version());
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("SCIE_BASE", &scie_base)
+            .env("SCIE_BOOT", "cache")
+            .args(["list", "--json"])
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    let listing = decode_output(output.stdout).unwrap();
+    let bindings_dir = listing
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(r#""bindings_dir": ""#))
+        .and_then(|bindings_dir| bindings_dir.strip_suffix("\","))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| panic!("Expected a bindings_dir in:\n{listing}"));
+    let venv = bindings_dir.join("venvs").join("2.16.0");
+    // N.B.: Forget the use of Pants 2.16.0 and age its venv so that it is garbage collected.
+    write_file(&bindings_dir.join("pants-usage.json"), false, "{}").unwrap();
+    execute(
+        Command::new("touch")
+            .args(["-t", "202001010000"])
+            .arg(&venv),
+    )
+    .unwrap();
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("SCIE_BASE", &scie_base)
+            .env("SCIE_BOOT", "cache")
+            .arg("gc")
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    let output = decode_output(output.stdout).unwrap();
+    assert!(output.contains("Deleted Pants 2.16.0 "), "{output}");
+    assert!(!venv.exists());
+
+    assert_eq!("2.16.0", pants_version());
+    assert!(venv.is_dir());
+}
+
+fn test_offline(scie_pants_scie: &Path) {
+    integration_test!("Verifying PANTS_BOOTSTRAP_OFFLINE fails fast on a cache miss");
+
+    let tmpdir = create_tempdir().unwrap();
+    write_file(
+        &tmpdir.path().join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"2.16.0\"\n",
+    )
+    .unwrap();
+
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .env("SCIE_BASE", tmpdir.path().join("nce"))
+            .current_dir(&tmpdir),
+        vec![
+            "PANTS_BOOTSTRAP_OFFLINE is set but Pants 2.16.0 is not fully installed in the \
+            scie-pants cache. Missing:",
+            "  + the Pants 2.16.0 venv at ",
+            "  + the CPython distribution cpython-3.9.",
+            "Run `SCIE_BOOT=prefetch scie-pants --pants-version 2.16.0` while online to \
+            install it.",
+        ],
+    );
+    assert_eq!(Some(68), output.status.code());
+
+    // N.B.: Pants older than 2.5 runs on CPython 3.8, but the configure binding still needs 3.9.
+    assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_VERSION", "2.4.0")
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .env("SCIE_BASE", tmpdir.path().join("nce"))
+            .current_dir(&tmpdir),
+        vec![
+            "  + the CPython distribution cpython-3.9.",
+            "  + the CPython distribution cpython-3.8.",
+        ],
+    );
+
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_SHA", "8e381dbf90cae57c5da2b223c577b36ca86cace9")
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .env("SCIE_BASE", tmpdir.path().join("nce"))
+            .current_dir(&tmpdir),
+        vec![
+            "PANTS_BOOTSTRAP_OFFLINE is set but Pants for \
+            PANTS_SHA=8e381dbf90cae57c5da2b223c577b36ca86cace9 is not installed in the scie-pants \
+            cache.",
+            "Run Pants with the same PANTS_SHA while online to install it.",
+        ],
+    );
+    assert_eq!(Some(68), output.status.code());
+
+    // Pants 2.16.0 is installed in the default cache by prior tests.
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .current_dir(&tmpdir)
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    assert_eq!("2.16.0", decode_output(output.stdout).unwrap().trim());
+}
+
+fn test_prefetch(scie_pants_scie: &Path) {
+    integration_test!(
+        "Verifying `SCIE_BOOT=prefetch scie-pants` installs Pants without running it"
+    );
+
+    let tmpdir = create_tempdir().unwrap();
+    let scie_base = tmpdir.path().join("nce");
+    let build_root = tmpdir.path().join("repo");
+    write_file(
+        &build_root.join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"2.16.0\"\n",
+    )
+    .unwrap();
+    let prefetch = || {
+        let output = execute(
+            Command::new(scie_pants_scie)
+                .env("SCIE_BASE", &scie_base)
+                .env("SCIE_BOOT", "prefetch")
+                .args(["--build-root", build_root.to_str().unwrap(), "--python"])
+                .stdout(Stdio::piped()),
+        )
+        .unwrap();
+        decode_output(output.stdout).unwrap()
+    };
+
+    let output = prefetch();
+    for expected in [
+        "CPython cpython-3.8.",
+        "CPython cpython-3.9.",
+        "Pants 2.16.0: installed at ",
+    ] {
+        assert!(output.contains(expected), "{output}");
+    }
+    let output = prefetch();
+    assert_eq!(
+        3,
+        output.matches(": already cached\n").count(),
+        "Expected everything to already be cached:\n{output}"
+    );
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("SCIE_BASE", &scie_base)
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .current_dir(&build_root)
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    assert_eq!("2.16.0", decode_output(output.stdout).unwrap().trim());
+
+    // N.B.: The install binding is keyed by the debugpy version; so prefetching must use the one
+    // the build root configures for a later launch there to find the install.
+    let scie_base = tmpdir.path().join("nce-debugpy");
+    let build_root = tmpdir.path().join("repo-debugpy");
+    write_file(
+        &build_root.join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"2.16.0\"\n[debugpy]\nversion = \"debugpy==1.6.5\"\n",
+    )
+    .unwrap();
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .env("SCIE_BASE", &scie_base)
+            .env("SCIE_BOOT", "prefetch")
+            .args(["--build-root", build_root.to_str().unwrap()])
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    let output = decode_output(output.stdout).unwrap();
+    assert!(
+        output.contains("Pants 2.16.0 [debugpy==1.6.5]: installed at "),
+        "{output}"
+    );
+
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("SCIE_BASE", &scie_base)
+            .env("PANTS_BOOTSTRAP_OFFLINE", "1")
+            .current_dir(&build_root)
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    assert_eq!("2.16.0", decode_output(output.stdout).unwrap().trim());
+}
+
+fn test_pants_source_debug(scie_pants_scie: &Path) {
+    integration_test!("Verifying PANTS_DEBUG support detection for PANTS_SOURCE checkouts");
+
+    let tmpdir = create_tempdir().unwrap();
+    let build_root = tmpdir.path().join("repo");
+    write_file(
+        &build_root.join("pants.toml"),
+        false,
+        "[GLOBAL]\npants_version = \"2.16.0\"\n",
+    )
+    .unwrap();
+    let checkout = |name: &str, pants_script: &str| {
+        let pants_source = tmpdir.path().join(name);
+        write_file(
+            &pants_source
+                .join("src")
+                .join("python")
+                .join("pants")
+                .join("VERSION"),
+            false,
+            "2.16.0",
+        )
+        .unwrap();
+        let pants = pants_source.join("pants");
+        write_file(&pants, false, pants_script).unwrap();
+        prepare_exe(&pants).unwrap();
+        pants_source
+    };
+
+    let commented = checkout(
+        "commented",
+        "#!/usr/bin/env bash\n# TODO: Support PANTS_DEBUG.\necho \"Pants from sources.\"\n",
+    );
+    let output = assert_failed_stderr_output(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_SOURCE", &commented)
+            .env("PANTS_DEBUG", "1")
+            .current_dir(&build_root),
+        vec!["PANTS_DEBUG is set but the ./pants script of the Pants source directory"],
+    );
+    assert_eq!(Some(78), output.status.code());
+
+    let supported = checkout(
+        "supported",
+        "#!/usr/bin/env bash\necho \"PANTS_DEBUG=${PANTS_DEBUG}\"\n",
+    );
+    let output = execute(
+        Command::new(scie_pants_scie)
+            .arg("-V")
+            .env("PANTS_SOURCE", &supported)
+            .env("PANTS_DEBUG", "1")
+            .current_dir(&build_root)
+            .stdout(Stdio::piped()),
+    )
+    .unwrap();
+    assert_eq!(
+        "PANTS_DEBUG=1",
+        decode_output(output.stdout).unwrap().trim()
+    );
+}
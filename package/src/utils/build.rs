@@ -174,7 +174,13 @@ impl BuildContext {
                 .env(
                     "PATH",
                     vec![self.cargo_output_bin_dir.to_str().unwrap(), env!("PATH")].join(PATHSEP),
-                ),
+                )
+                // N.B.: These are baked into the scie-pants binary for its self-report.
+                .env(
+                    "SCIE_JUMP_TAG",
+                    a_scie_project_tag(&self.scie_jump_repo, SCIE_JUMP_TAG),
+                )
+                .env("PTEX_TAG", a_scie_project_tag(&self.ptex_repo, PTEX_TAG)),
         )?;
         Ok(self
             .cargo_output_bin_dir
@@ -188,6 +194,15 @@ pub(crate) struct SkinnyScieTools {
     pub(crate) scie_jump: PathBuf,
 }
 
+/// Describes the version of an a-scie project binary; either its release tag or the local repo it
+/// was built from.
+fn a_scie_project_tag(a_scie_project_repo: &Option<PathBuf>, tag: &str) -> String {
+    match a_scie_project_repo {
+        Some(repo) => format!("built from {repo}", repo = repo.display()),
+        None => tag.to_string(),
+    }
+}
+
 fn build_a_scie_project(a_scie_project_repo: &Path, target: &str, dest_dir: &Path) -> Result<()> {
     execute(Command::new(CARGO).args([
         "run",
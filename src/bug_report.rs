@@ -0,0 +1,240 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::env;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::build_root::BuildRoot;
+use crate::config::PantsConfig;
+use crate::{cache, get_launch_process, lift, LaunchMode};
+
+/// The number of trailing lines of each bootstrap log to include in a bug report.
+const LOG_TAIL_LINES: usize = 200;
+
+/// The `pants.toml` tables the launcher reads along with the keys it reads from each; `None` means
+/// the launcher reads the whole table. Only these are included in a bug report since other
+/// sections may hold credentials.
+const LAUNCHER_CONFIG: &[(&str, Option<&str>)] = &[
+    ("GLOBAL", Some("pants_version")),
+    ("debugpy", Some("version")),
+    ("DEFAULT", Some("delegate_bootstrap")),
+    ("scie-pants", None),
+];
+
+#[derive(Serialize)]
+struct Platform {
+    os: &'static str,
+    arch: &'static str,
+}
+
+#[derive(Serialize)]
+struct Report {
+    scie_pants_version: &'static str,
+    platform: Platform,
+    build_root: Option<PathBuf>,
+    pants_toml: Option<PathBuf>,
+    pants_bootstrap_sha256: Option<String>,
+    /// Problems encountered gathering information; a partial report is still useful.
+    errors: Vec<String>,
+}
+
+struct BugReport {
+    archive: tar::Builder<GzEncoder<File>>,
+    mtime: u64,
+    report: Report,
+}
+
+impl BugReport {
+    fn append(&mut self, path: &str, contents: &[u8]) -> Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(self.mtime);
+        self.archive
+            .append_data(
+                &mut header,
+                Path::new("scie-pants-bug-report").join(path),
+                contents,
+            )
+            .with_context(|| format!("Failed to add {path} to the bug report."))
+    }
+
+    /// Appends the given file to the bug report if it could be gathered and records the error
+    /// otherwise.
+    fn append_or_record(&mut self, path: &str, contents: Result<Vec<u8>>) -> Result<()> {
+        match contents {
+            Ok(contents) => self.append(path, &contents),
+            Err(err) => {
+                self.report
+                    .errors
+                    .push(format!("Failed to gather {path}: {err:#}"));
+                Ok(())
+            }
+        }
+    }
+}
+
+fn launch_plan() -> Result<Vec<u8>> {
+    // N.B.: Process redacts the values of sensitive env vars when serialized.
+    serde_json::to_vec_pretty(&get_launch_process(LaunchMode::Plan)?)
+        .context("Failed to serialize the launch plan as JSON.")
+}
+
+fn launcher_config(pants_toml: &Path) -> Result<Vec<u8>> {
+    let contents = std::fs::read_to_string(pants_toml)
+        .with_context(|| format!("Failed to read {path}", path = pants_toml.display()))?;
+    let config: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse {path}", path = pants_toml.display()))?;
+    let mut excerpt = toml::Table::new();
+    for (table_name, key) in LAUNCHER_CONFIG {
+        let Some(toml::Value::Table(table)) = config.get(*table_name) else {
+            continue;
+        };
+        let table = match key {
+            Some(key) => table
+                .iter()
+                .filter(|(name, _)| name == key)
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            None => table.clone(),
+        };
+        if !table.is_empty() {
+            excerpt.insert(table_name.to_string(), toml::Value::Table(table));
+        }
+    }
+    Ok(toml::to_string(&excerpt)
+        .context("Failed to serialize the launcher config.")?
+        .into_bytes())
+}
+
+fn sha256(path: &Path) -> Result<String> {
+    let mut file = File::open(path)
+        .with_context(|| format!("Failed to open {path}", path = path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .with_context(|| format!("Failed to hash {path}", path = path.display()))?;
+    Ok(format!("{digest:x}", digest = hasher.finalize()))
+}
+
+fn log_tail(log: &Path) -> Result<Vec<u8>> {
+    let contents =
+        std::fs::read(log).with_context(|| format!("Failed to read {log}", log = log.display()))?;
+    let contents = String::from_utf8_lossy(&contents);
+    let lines = contents.lines().collect::<Vec<_>>();
+    let tail = &lines[lines.len().saturating_sub(LOG_TAIL_LINES)..];
+    Ok(tail.join("\n").into_bytes())
+}
+
+/// Finds the bootstrap logs written by the scie-pants tools under the nce bindings dir; both the
+/// per-tool logs and the per-Pants-version install logs.
+fn bootstrap_logs(bindings_dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut logs = vec![];
+    if let Ok(entries) = std::fs::read_dir(bindings_dir.join("logs")) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|ext| ext == "log").unwrap_or_default() {
+                logs.push((
+                    format!("logs/{name}", name = entry.file_name().to_string_lossy()),
+                    path,
+                ));
+            }
+        }
+    }
+    if let Ok(entries) = std::fs::read_dir(bindings_dir.join("venvs")) {
+        for entry in entries.flatten() {
+            let path = entry.path().join("pants-install.log");
+            if path.is_file() {
+                logs.push((
+                    format!(
+                        "logs/venvs/{version}/pants-install.log",
+                        version = entry.file_name().to_string_lossy()
+                    ),
+                    path,
+                ));
+            }
+        }
+    }
+    logs.sort();
+    logs
+}
+
+pub(crate) fn create(output: Option<PathBuf>) -> Result<()> {
+    let timestamp = cache::now()?;
+    let output = output
+        .unwrap_or_else(|| PathBuf::from(format!("scie-pants-bug-report-{timestamp}.tar.gz")));
+    let file = File::create(&output)
+        .with_context(|| format!("Failed to create {output}", output = output.display()))?;
+    let mut bug_report = BugReport {
+        archive: tar::Builder::new(GzEncoder::new(file, Compression::default())),
+        mtime: timestamp,
+        report: Report {
+            scie_pants_version: env!("CARGO_PKG_VERSION"),
+            platform: Platform {
+                os: env::consts::OS,
+                arch: env::consts::ARCH,
+            },
+            build_root: None,
+            pants_toml: None,
+            pants_bootstrap_sha256: None,
+            errors: vec![],
+        },
+    };
+
+    bug_report.append_or_record("lift.json", lift::manifest())?;
+    bug_report.append_or_record("launch-plan.json", launch_plan())?;
+
+    match BuildRoot::find(None) {
+        Ok(build_root) => {
+            let (pants_toml, _) = PantsConfig::locate(&build_root);
+            if pants_toml.is_file() {
+                bug_report.append_or_record("pants.toml", launcher_config(&pants_toml))?;
+                bug_report.report.pants_toml = Some(pants_toml);
+            }
+            let pants_bootstrap = build_root.join(".pants.bootstrap");
+            if pants_bootstrap.is_file() {
+                match sha256(&pants_bootstrap) {
+                    Ok(hash) => bug_report.report.pants_bootstrap_sha256 = Some(hash),
+                    Err(err) => bug_report.report.errors.push(format!("{err:#}")),
+                }
+            }
+            bug_report.report.build_root = Some(build_root.to_path_buf());
+        }
+        Err(err) => bug_report.report.errors.push(format!("{err:#}")),
+    }
+
+    match env::var_os("SCIE_PANTS_BINDINGS") {
+        Some(bindings_dir) => {
+            for (name, log) in bootstrap_logs(Path::new(&bindings_dir)) {
+                bug_report.append_or_record(&name, log_tail(&log))?;
+            }
+        }
+        None => bug_report
+            .report
+            .errors
+            .push("The SCIE_PANTS_BINDINGS env var is not set; no logs were gathered.".into()),
+    }
+
+    let report = serde_json::to_vec_pretty(&bug_report.report)
+        .context("Failed to serialize the bug report summary as JSON.")?;
+    bug_report.append("report.json", &report)?;
+    bug_report
+        .archive
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .with_context(|| {
+            format!(
+                "Failed to finish writing {output}",
+                output = output.display()
+            )
+        })?;
+
+    println!("Wrote bug report to {output}", output = output.display());
+    Ok(())
+}
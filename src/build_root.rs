@@ -7,6 +7,8 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use logging_timer::time;
 
+use crate::error::{ErrorKind, WithErrorKind};
+
 pub(crate) struct BuildRoot(PathBuf);
 
 impl BuildRoot {
@@ -25,12 +27,15 @@ impl BuildRoot {
                     return Ok(BuildRoot(cwd.to_path_buf()));
                 }
             }
-            cwd = cwd.parent().with_context(|| {
-                format!(
+            cwd = cwd
+                .parent()
+                .with_context(|| {
+                    format!(
                     "Failed to find pants.toml, BUILDROOT or BUILD_ROOT starting at {start_search}",
                     start_search = start_search.display()
                 )
-            })?;
+                })
+                .error_kind(ErrorKind::NoBuildRoot)?;
         }
     }
 }
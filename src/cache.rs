@@ -0,0 +1,720 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::build_root::BuildRoot;
+use crate::config::PantsConfig;
+use crate::error::{ErrorKind, WithErrorKind};
+use crate::lift;
+use crate::version::Version;
+
+/// Returns the current time in seconds since the UNIX epoch.
+pub(crate) fn now() -> Result<u64> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("The system clock is set before the UNIX epoch.")?
+        .as_secs())
+}
+
+/// Writes the given value as JSON to the given path, creating its parent dir if needed.
+pub(crate) fn store_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let parent = path.parent().with_context(|| {
+        format!(
+            "Failed to determine the parent of {path}",
+            path = path.display()
+        )
+    })?;
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create {parent}", parent = parent.display()))?;
+    // N.B.: Concurrent launches may race to update the files we keep in the bindings dir; so we
+    // write them atomically.
+    let mut file = tempfile::NamedTempFile::new_in(parent)?;
+    file.write_all(&serde_json::to_vec_pretty(value)?)?;
+    file.persist(path)
+        .with_context(|| format!("Failed to write {path}", path = path.display()))?;
+    Ok(())
+}
+
+pub(crate) fn bindings_dir() -> Result<PathBuf> {
+    env::var_os("SCIE_PANTS_BINDINGS")
+        .map(PathBuf::from)
+        .context("Failed to retrieve the scie bindings dir from the environment.")
+        .error_kind(ErrorKind::MissingScie)
+}
+
+#[derive(Deserialize, Serialize)]
+struct BuildRootUse {
+    pants_version: String,
+    last_used: u64,
+}
+
+/// The record of Pants launches kept in the nce bindings dir; the Pants venvs themselves carry no
+/// trace of when they were last used.
+#[derive(Default, Deserialize, Serialize)]
+struct UsageLog {
+    /// The last launch time of each Pants version in seconds since the UNIX epoch.
+    #[serde(default)]
+    pants_versions: BTreeMap<String, u64>,
+    /// The Pants version each build root was last launched with and when.
+    #[serde(default)]
+    build_roots: BTreeMap<PathBuf, BuildRootUse>,
+}
+
+impl UsageLog {
+    fn path(bindings_dir: &Path) -> PathBuf {
+        bindings_dir.join("pants-usage.json")
+    }
+
+    fn load(bindings_dir: &Path) -> Self {
+        std::fs::read(Self::path(bindings_dir))
+            .ok()
+            .and_then(|contents| serde_json::from_slice(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn store(&self, bindings_dir: &Path) -> Result<()> {
+        store_json(&Self::path(bindings_dir), self).context("Failed to update the Pants usage log.")
+    }
+
+    /// Forgets the Pants versions and build roots last used before the given time.
+    fn prune(&mut self, since: u64) {
+        self.pants_versions
+            .retain(|_, last_used| *last_used >= since);
+        self.build_roots
+            .retain(|_, build_root_use| build_root_use.last_used >= since);
+    }
+}
+
+/// Records that the given Pants version is being launched, in the given build root if any.
+pub(crate) fn record_use(pants_version: &str, build_root: Option<&Path>) -> Result<()> {
+    if let Some(bindings_dir) = env::var_os("SCIE_PANTS_BINDINGS") {
+        let bindings_dir = Path::new(&bindings_dir);
+        let now = now()?;
+        let mut usage_log = UsageLog::load(bindings_dir);
+        usage_log
+            .pants_versions
+            .insert(pants_version.to_string(), now);
+        if let Some(build_root) = build_root {
+            usage_log.build_roots.insert(
+                build_root.to_path_buf(),
+                BuildRootUse {
+                    pants_version: pants_version.to_string(),
+                    last_used: now,
+                },
+            );
+        }
+        usage_log.store(bindings_dir)?;
+    }
+    Ok(())
+}
+
+/// Returns the total size of the files under the given path without following symlinks.
+fn disk_usage(path: &Path) -> u64 {
+    let metadata = match path.symlink_metadata() {
+        Ok(metadata) => metadata,
+        Err(_) => return 0,
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| disk_usage(&entry.path()))
+        .sum()
+}
+
+fn modified(path: &Path) -> Option<u64> {
+    path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|age| age.as_secs())
+}
+
+#[derive(Serialize)]
+struct PantsVenv {
+    name: String,
+    pants_version: String,
+    path: PathBuf,
+    size: u64,
+    /// When the venv was created in seconds since the UNIX epoch.
+    installed: Option<u64>,
+    /// When the venv's Pants version was last launched in seconds since the UNIX epoch, if known.
+    last_used: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct CacheEntry {
+    name: String,
+    path: PathBuf,
+    size: u64,
+}
+
+#[derive(Serialize)]
+struct Listing {
+    bindings_dir: PathBuf,
+    pants_venvs: Vec<PantsVenv>,
+    python_distributions: Vec<CacheEntry>,
+    pex_root: Option<CacheEntry>,
+    total_size: u64,
+    /// Cache entries that could not be inspected.
+    errors: Vec<String>,
+}
+
+/// The Pants venvs are named for their Pants version with an optional `-<debugpy requirement>`
+/// suffix when installed for debugging.
+fn venv_pants_version(name: &str) -> &str {
+    name.split_once('-')
+        .map(|(version, _)| version)
+        .unwrap_or(name)
+}
+
+fn pants_venvs(bindings_dir: &Path, usage_log: &UsageLog) -> Vec<PantsVenv> {
+    let mut venvs = std::fs::read_dir(bindings_dir.join("venvs"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let pants_version = venv_pants_version(&name).to_string();
+            PantsVenv {
+                size: disk_usage(&path),
+                installed: modified(&path),
+                last_used: usage_log.pants_versions.get(&pants_version).copied(),
+                name,
+                pants_version,
+                path,
+            }
+        })
+        .collect::<Vec<_>>();
+    venvs.sort_by(|a, b| {
+        match (
+            Version::parse(&a.pants_version),
+            Version::parse(&b.pants_version),
+        ) {
+            (Some(a_version), Some(b_version)) => a_version.cmp(&b_version),
+            _ => a.pants_version.cmp(&b.pants_version),
+        }
+        .then_with(|| a.name.cmp(&b.name))
+    });
+    venvs
+}
+
+/// Determines the nce cache dir from the bindings dir; the bindings dir is
+/// `<nce>/<lift manifest hash>/bindings` and scie-jump extracts each file to `<nce>/<file hash>`.
+fn nce_dir(bindings_dir: &Path) -> Result<&Path> {
+    bindings_dir
+        .parent()
+        .and_then(Path::parent)
+        .with_context(|| {
+            format!(
+                "Failed to determine the nce cache dir from the bindings dir {bindings_dir}",
+                bindings_dir = bindings_dir.display()
+            )
+        })
+}
+
+/// Finds the CPython distributions packaged in this scie that have been extracted to the nce cache.
+fn python_distributions(bindings_dir: &Path) -> Result<Vec<CacheEntry>> {
+    let nce_dir = nce_dir(bindings_dir)?;
+    Ok(lift::files()?
+        .into_iter()
+        .filter(|file| file.name.starts_with("cpython-"))
+        .filter_map(|file| {
+            let path = nce_dir.join(file.hash?);
+            path.exists().then(|| CacheEntry {
+                name: file.name,
+                size: disk_usage(&path),
+                path,
+            })
+        })
+        .collect())
+}
+
+fn human_size(size: u64) -> String {
+    let mut size = size as f64;
+    for unit in ["B", "KiB", "MiB", "GiB"] {
+        if size < 1024.0 {
+            return if unit == "B" {
+                format!("{size} {unit}")
+            } else {
+                format!("{size:.1} {unit}")
+            };
+        }
+        size /= 1024.0;
+    }
+    format!("{size:.1} TiB")
+}
+
+fn human_age(timestamp: u64, now: u64) -> String {
+    let (count, unit) = match now.saturating_sub(timestamp) {
+        age if age < 60 => return "just now".to_string(),
+        age if age < 60 * 60 => (age / 60, "minute"),
+        age if age < 24 * 60 * 60 => (age / (60 * 60), "hour"),
+        age => (age / (24 * 60 * 60), "day"),
+    };
+    format!(
+        "{count} {unit}{plural} ago",
+        plural = if count == 1 { "" } else { "s" }
+    )
+}
+
+fn print_table(rows: Vec<Vec<String>>) {
+    let mut widths = vec![];
+    for row in &rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("  {line}", line = line.trim_end());
+    }
+}
+
+fn print_listing(listing: &Listing) -> Result<()> {
+    let now = now()?;
+    println!(
+        "Pants installs in {venvs_dir}:",
+        venvs_dir = listing.bindings_dir.join("venvs").display()
+    );
+    if listing.pants_venvs.is_empty() {
+        println!("  (none)");
+    } else {
+        let mut rows = vec![vec![
+            "Pants version".to_string(),
+            "Size".to_string(),
+            "Last used".to_string(),
+        ]];
+        for venv in &listing.pants_venvs {
+            rows.push(vec![
+                venv.name.clone(),
+                human_size(venv.size),
+                venv.last_used
+                    .map(|last_used| human_age(last_used, now))
+                    .unwrap_or_else(|| "unknown".to_string()),
+            ]);
+        }
+        print_table(rows);
+    }
+    println!("CPython distributions:");
+    if listing.python_distributions.is_empty() {
+        println!("  (none)");
+    } else {
+        print_table(
+            listing
+                .python_distributions
+                .iter()
+                .map(|distribution| vec![distribution.name.clone(), human_size(distribution.size)])
+                .collect(),
+        );
+    }
+    println!("PEX root:");
+    match listing.pex_root {
+        Some(ref pex_root) => print_table(vec![vec![
+            pex_root.path.display().to_string(),
+            human_size(pex_root.size),
+        ]]),
+        None => println!("  (none)"),
+    }
+    println!(
+        "Total size: {total_size}",
+        total_size = human_size(listing.total_size)
+    );
+    for error in &listing.errors {
+        eprintln!("Warning: {error}");
+    }
+    Ok(())
+}
+
+/// Lists the Pants installs, CPython distributions and PEX root in the nce cache.
+pub(crate) fn list(json: bool) -> Result<()> {
+    let bindings_dir = bindings_dir()?;
+    let mut errors = vec![];
+    let pants_venvs = pants_venvs(&bindings_dir, &UsageLog::load(&bindings_dir));
+    let python_distributions = python_distributions(&bindings_dir).unwrap_or_else(|err| {
+        errors.push(format!("{err:#}"));
+        vec![]
+    });
+    let pex_root = bindings_dir.join("pex_root");
+    let pex_root = pex_root.is_dir().then(|| CacheEntry {
+        name: "pex_root".to_string(),
+        size: disk_usage(&pex_root),
+        path: pex_root,
+    });
+    let total_size = pants_venvs.iter().map(|venv| venv.size).sum::<u64>()
+        + python_distributions
+            .iter()
+            .chain(pex_root.as_ref())
+            .map(|entry| entry.size)
+            .sum::<u64>();
+    let listing = Listing {
+        bindings_dir,
+        pants_venvs,
+        python_distributions,
+        pex_root,
+        total_size,
+        errors,
+    };
+    if json {
+        println!(
+            "{listing}",
+            listing = serde_json::to_string_pretty(&listing)
+                .context("Failed to serialize the cache listing as JSON.")?
+        );
+        Ok(())
+    } else {
+        print_listing(&listing)
+    }
+}
+
+/// The CPython the configure binding selects to run the given Pants version with.
+pub(crate) fn python_for(pants_version: &str) -> &'static str {
+    let pants_2_5 = Version::parse("2.5").expect("2.5 is a valid version.");
+    match Version::parse(pants_version) {
+        Some(version) if version < pants_2_5 => "python3.8",
+        _ => "python3.9",
+    }
+}
+
+/// The venv the install binding creates for the given Pants version; a separate venv is created
+/// when installing for debugging with the given debugpy requirement.
+pub(crate) fn venv_dir(
+    bindings_dir: &Path,
+    pants_version: &str,
+    debugpy_requirement: Option<&str>,
+) -> PathBuf {
+    let venv_name = match debugpy_requirement {
+        Some(debugpy_requirement) => format!("{pants_version}-{debugpy_requirement}"),
+        None => pants_version.to_string(),
+    };
+    bindings_dir.join("venvs").join(venv_name)
+}
+
+/// Finds the Pants version of the venv installed for the given `PANTS_SHA`, if any. The configure
+/// binding gives these versions a `+git<abbreviated sha>` local version label.
+pub(crate) fn sha_pants_version(sha: &str) -> Result<Option<String>> {
+    let local_version = format!("+git{abbreviated_sha}", abbreviated_sha = &sha[..8]);
+    Ok(std::fs::read_dir(bindings_dir()?.join("venvs"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| venv_pants_version(&entry.file_name().to_string_lossy()).to_string())
+        .find(|pants_version| pants_version.ends_with(&local_version)))
+}
+
+/// Locates the given CPython distribution for the current platform, e.g.: `python3.9`, in the nce
+/// cache; returning its name and the path scie-jump extracts it to.
+pub(crate) fn python_distribution(bindings_dir: &Path, python: &str) -> Result<(String, PathBuf)> {
+    let python_key = format!(
+        "{python}-{os}-{arch}",
+        os = env::consts::OS,
+        arch = env::consts::ARCH
+    );
+    let python_distribution = lift::files()?
+        .into_iter()
+        .find(|file| file.key.as_deref() == Some(python_key.as_str()))
+        .with_context(|| format!("This scie does not contain the {python_key} distribution."))?;
+    let hash = python_distribution.hash.with_context(|| {
+        format!(
+            "The lift manifest has no hash for {name}.",
+            name = python_distribution.name
+        )
+    })?;
+    Ok((python_distribution.name, nce_dir(bindings_dir)?.join(hash)))
+}
+
+/// Verifies the Pants venv and the CPython distributions needed to launch the given Pants version
+/// are in the nce cache; so that no network access is needed.
+///
+/// The debugpy requirement should be given when launching Pants for debugging, which uses its own
+/// venv. The venv check is skipped if Pants is not being launched.
+pub(crate) fn check_offline(
+    pants_version: &str,
+    debugpy_requirement: Option<&str>,
+    needs_venv: bool,
+) -> Result<()> {
+    let bindings_dir = bindings_dir()?;
+    let mut missing = vec![];
+    if needs_venv {
+        let venv = venv_dir(&bindings_dir, pants_version, debugpy_requirement);
+        if !venv.is_dir() {
+            missing.push(format!(
+                "the Pants {pants_version} venv at {venv}",
+                venv = venv.display()
+            ));
+        }
+    }
+    // N.B.: The configure binding always runs on python3.9 to pick the CPython for Pants.
+    let mut pythons = vec!["python3.9"];
+    if python_for(pants_version) != "python3.9" {
+        pythons.push(python_for(pants_version));
+    }
+    for python in pythons {
+        let (name, path) = python_distribution(&bindings_dir, python)?;
+        if !path.exists() {
+            missing.push(format!(
+                "the CPython distribution {name} at {path}",
+                path = path.display()
+            ));
+        }
+    }
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(anyhow!(
+        "PANTS_BOOTSTRAP_OFFLINE is set but Pants {pants_version} is not fully installed in the \
+        scie-pants cache. Missing:\n\
+        {missing}\n\
+        {install_hint}",
+        install_hint = if pants_version.contains("+git") {
+            "Run Pants with the same PANTS_SHA while online to install it.".to_string()
+        } else {
+            format!(
+                "Run `SCIE_BOOT=prefetch scie-pants --pants-version {pants_version}` while online \
+                to install it."
+            )
+        },
+        missing = missing
+            .iter()
+            .map(|item| format!("  + {item}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))
+    .error_kind(ErrorKind::Offline)
+}
+
+/// Exclusive locks on the `.lck` files scie-jump guards its bindings with. The install binding that
+/// creates Pants venvs runs under one of these; so holding them all ensures no install is racing a
+/// garbage collection and blocks new installs until it completes.
+struct BindingLocks {
+    locks_dir: PathBuf,
+    _lock_files: Vec<std::fs::File>,
+}
+
+impl BindingLocks {
+    /// scie-jump keeps its binding locks, along with the recorded output of each binding that
+    /// completed, in `<nce>/<lift manifest hash>/locks`.
+    fn locks_dir(bindings_dir: &Path) -> Result<PathBuf> {
+        bindings_dir
+            .parent()
+            .map(|lift_dir| lift_dir.join("locks"))
+            .with_context(|| {
+                format!(
+                    "Failed to determine the scie-jump binding locks dir from the bindings dir \
+                    {bindings_dir}",
+                    bindings_dir = bindings_dir.display()
+                )
+            })
+    }
+
+    fn entries(locks_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut paths = std::fs::read_dir(locks_dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .with_context(|| {
+                format!(
+                    "Failed to read the scie-jump binding locks dir {locks_dir}",
+                    locks_dir = locks_dir.display()
+                )
+            })?;
+        paths.sort();
+        Ok(paths)
+    }
+
+    #[cfg(unix)]
+    fn acquire(bindings_dir: &Path) -> Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        use nix::errno::Errno;
+        use nix::fcntl::{flock, FlockArg};
+
+        let locks_dir = Self::locks_dir(bindings_dir)?;
+        let mut lock_files = vec![];
+        for lock_path in Self::entries(&locks_dir)?
+            .into_iter()
+            .filter(|path| path.extension().map(|ext| ext == "lck").unwrap_or_default())
+        {
+            let lock_file = std::fs::OpenOptions::new()
+                .read(true)
+                .open(&lock_path)
+                .with_context(|| format!("Failed to open {path}", path = lock_path.display()))?;
+            match flock(lock_file.as_raw_fd(), FlockArg::LockExclusiveNonblock) {
+                Ok(()) => lock_files.push(lock_file),
+                Err(Errno::EWOULDBLOCK) => bail!(
+                    "The scie-jump binding lock {path} is held; a Pants install may be in \
+                    progress. Try again once it completes.",
+                    path = lock_path.display()
+                ),
+                Err(err) => {
+                    return Err(err).with_context(|| {
+                        format!("Failed to lock {path}", path = lock_path.display())
+                    })
+                }
+            }
+        }
+        Ok(Self {
+            locks_dir,
+            _lock_files: lock_files,
+        })
+    }
+
+    #[cfg(windows)]
+    fn acquire(_bindings_dir: &Path) -> Result<Self> {
+        bail!("Garbage collecting the Pants installs cache is not supported on Windows yet.")
+    }
+
+    /// Removes scie-jump's record of the install bindings that created the given venv. Without
+    /// this, scie-jump would consider the install done and launch Pants from the deleted venv.
+    fn forget_installs(&self, venv: &Path) -> Result<()> {
+        for path in Self::entries(&self.locks_dir)? {
+            let is_install_output = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with("install-") && !name.ends_with(".lck"))
+                .unwrap_or_default();
+            if !is_install_output || !path.is_file() {
+                continue;
+            }
+            let output = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {path}", path = path.display()))?;
+            if output
+                .lines()
+                .filter_map(|line| line.strip_prefix("VIRTUAL_ENV="))
+                .any(|virtual_env| Path::new(virtual_env) == venv)
+            {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to delete {path}", path = path.display()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finds the Pants versions pinned by the build roots used since the given time; both the version
+/// each was last launched with and the version its Pants config pins now.
+fn pinned_versions(usage_log: &UsageLog, since: u64) -> BTreeMap<String, BTreeSet<PathBuf>> {
+    let mut pinned = BTreeMap::<String, BTreeSet<PathBuf>>::new();
+    for (build_root, build_root_use) in &usage_log.build_roots {
+        if build_root_use.last_used < since {
+            continue;
+        }
+        let current_version = BuildRoot::find(Some(build_root.clone()))
+            .and_then(PantsConfig::parse_if_present)
+            .ok()
+            .flatten()
+            .and_then(|pants_config| pants_config.package_version());
+        for pants_version in [Some(build_root_use.pants_version.clone()), current_version]
+            .into_iter()
+            .flatten()
+        {
+            pinned
+                .entry(pants_version)
+                .or_default()
+                .insert(build_root.clone());
+        }
+    }
+    pinned
+}
+
+/// Deletes the Pants venvs not used in the given number of days unless pinned by a build root used
+/// in that time.
+pub(crate) fn gc(unused_days: u64, dry_run: bool) -> Result<()> {
+    let bindings_dir = bindings_dir()?;
+    let usage_log = UsageLog::load(&bindings_dir);
+    let now = now()?;
+    let since = now.saturating_sub(unused_days * 24 * 60 * 60);
+    let pinned = pinned_versions(&usage_log, since);
+
+    let mut unused = vec![];
+    for venv in pants_venvs(&bindings_dir, &usage_log) {
+        let last_used = venv.last_used.max(venv.installed);
+        if last_used
+            .map(|last_used| last_used >= since)
+            .unwrap_or(true)
+        {
+            continue;
+        }
+        let last_used = last_used
+            .map(|last_used| human_age(last_used, now))
+            .unwrap_or_else(|| "unknown".to_string());
+        if let Some(build_roots) = pinned.get(&venv.pants_version) {
+            println!(
+                "Keeping Pants {name} (last used {last_used}) since it is pinned by {build_roots}.",
+                name = venv.name,
+                build_roots = build_roots
+                    .iter()
+                    .map(|build_root| build_root.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            continue;
+        }
+        unused.push((venv, last_used));
+    }
+
+    let locks = if dry_run || unused.is_empty() {
+        None
+    } else {
+        Some(BindingLocks::acquire(&bindings_dir)?)
+    };
+    let mut freed = 0;
+    for (venv, last_used) in unused {
+        if let Some(ref locks) = locks {
+            // N.B.: We forget the install first; so an interrupted gc leads to a re-install instead
+            // of a launch from a partially deleted venv.
+            locks.forget_installs(&venv.path)?;
+            std::fs::remove_dir_all(&venv.path)
+                .with_context(|| format!("Failed to delete {path}", path = venv.path.display()))?;
+            println!(
+                "Deleted Pants {name} (last used {last_used}) from {path}, freeing {size}.",
+                name = venv.name,
+                path = venv.path.display(),
+                size = human_size(venv.size)
+            );
+        } else {
+            println!(
+                "Would delete Pants {name} (last used {last_used}) from {path}, freeing {size}.",
+                name = venv.name,
+                path = venv.path.display(),
+                size = human_size(venv.size)
+            );
+        }
+        freed += venv.size;
+    }
+    if !dry_run {
+        // N.B.: We re-load the usage log to lose as few concurrent updates to it as possible.
+        let mut usage_log = UsageLog::load(&bindings_dir);
+        usage_log.prune(since);
+        usage_log.store(&bindings_dir)?;
+    }
+    println!(
+        "{action} {freed} in total.",
+        action = if dry_run { "Would free" } else { "Freed" },
+        freed = human_size(freed)
+    );
+    Ok(())
+}
@@ -0,0 +1,256 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use crate::init::{self, Backend, InitOptions};
+use crate::prefetch::{self, PrefetchOptions};
+use crate::run_many::{self, RunManyOptions};
+use crate::upgrade::{self, UpgradeOptions, UpgradeTarget};
+use crate::{bug_report, cache, set_version};
+
+/// Commands implemented by scie-pants itself instead of by Pants. Each is exposed as a boot command
+/// in the scie lift manifest that re-executes scie-pants with `SCIE_PANTS_COMMAND` set to the
+/// command name; so they are invoked as `SCIE_BOOT=<command> scie-pants [ARGS]`.
+#[derive(Parser)]
+#[command(name = "scie-pants", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Writes a tarball with the information needed to file a scie-pants bug report.
+    BugReport {
+        #[arg(
+            long,
+            help = "The path to write the bug report tarball to. By default, a \
+            scie-pants-bug-report-<timestamp>.tar.gz file is written to the current directory."
+        )]
+        output: Option<PathBuf>,
+    },
+    /// Manages the Pants installs and other files scie-pants keeps in the nce cache.
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Scaffolds a new Pants project.
+    Init {
+        #[arg(
+            long,
+            help = "The directory to initialize as a Pants project. By default, the current \
+            directory."
+        )]
+        dir: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "The Pants version to configure; either an exact version or an alias like \
+            `latest-stable` or `2.17`. By default, the latest stable Pants release."
+        )]
+        pants_version: Option<String>,
+        #[arg(
+            long = "backend",
+            value_enum,
+            help = "A Pants backend to enable. Can be specified multiple times."
+        )]
+        backends: Vec<Backend>,
+        #[arg(
+            long,
+            help = "The interpreter constraint for your Python code, e.g.: `CPython==3.11.*`. \
+            Requires `--backend python`."
+        )]
+        interpreter_constraint: Option<String>,
+        #[arg(long, help = "Also write a BUILD_ROOT marker file.")]
+        build_root_marker: bool,
+        #[arg(long, help = "Add the Pants workspace files to the .gitignore file.")]
+        gitignore: bool,
+        #[arg(long, help = "Overwrite existing files.")]
+        force: bool,
+    },
+    /// Installs Pants versions and CPython distributions into the cache without running Pants.
+    Prefetch {
+        #[arg(
+            long = "pants-version",
+            help = "A Pants version to install; either an exact version or an alias like \
+            `latest-stable` or `2.17`. Can be specified multiple times."
+        )]
+        pants_versions: Vec<String>,
+        #[arg(
+            long = "build-root",
+            help = "A build root whose configured Pants version should be installed. Can be \
+            specified multiple times. If no Pants versions or build roots are given, the Pants \
+            version of the current build root is installed."
+        )]
+        build_roots: Vec<PathBuf>,
+        #[arg(
+            long,
+            help = "Also fetch all the CPython distributions Pants can run with on this platform."
+        )]
+        python: bool,
+    },
+    /// Runs Pants with the given args in each of the given build roots and summarizes the results.
+    RunMany {
+        #[arg(help = "The build root directories to run Pants in.")]
+        dirs: Vec<PathBuf>,
+        #[arg(
+            long = "glob",
+            help = "A glob pattern matching build root directories to run Pants in, e.g.: \
+            `../*`. Can be specified multiple times."
+        )]
+        globs: Vec<String>,
+        #[arg(
+            long,
+            default_value_t = 1,
+            help = "The maximum number of build roots to run Pants in at once. When greater than \
+            one, the output of each Pants run is printed when it completes."
+        )]
+        jobs: usize,
+        #[arg(last = true, help = "The arguments to pass to Pants.")]
+        pants_args: Vec<OsString>,
+    },
+    /// Sets the Pants version in `pants.toml`, preserving its comments and formatting.
+    SetVersion {
+        #[arg(help = "The Pants version to set.")]
+        pants_version: String,
+        #[arg(
+            long,
+            help = "The Pants config file to edit. By default, the file named by the PANTS_TOML \
+            env var or else the pants.toml in the build root."
+        )]
+        config: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Don't write the file; instead exit non-zero if it would change."
+        )]
+        check: bool,
+    },
+    /// Upgrades the Pants version in `pants.toml` and lists the release notes to review.
+    UpgradePants {
+        #[arg(
+            long,
+            default_value = "latest-patch",
+            help = "The release to upgrade to: `latest-patch` for the newest stable release in the \
+            current release line, `next-minor` for the newest stable release in the next release \
+            line or else a specific version or version alias."
+        )]
+        to: UpgradeTarget,
+        #[arg(
+            long,
+            help = "The Pants config file to edit. By default, the file named by the PANTS_TOML \
+            env var or else the pants.toml in the build root."
+        )]
+        config: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "A file with Pants release data in the PyPI JSON API format to use instead of \
+            fetching it from https://pypi.org/pypi/pantsbuild.pants/json."
+        )]
+        releases_file: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Show the change to the Pants config file without writing it."
+        )]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Lists the installed Pants versions, CPython distributions and PEX root with their sizes.
+    List {
+        #[arg(long, help = "Print the listing as JSON.")]
+        json: bool,
+    },
+    /// Deletes the installed Pants versions that have not been used recently.
+    Gc {
+        #[arg(
+            long,
+            default_value_t = 30,
+            help = "Delete the Pants versions not used in this many days. Versions pinned by build \
+            roots Pants was run in during that time are always kept."
+        )]
+        unused_days: u64,
+        #[arg(long, help = "Show what would be deleted without deleting it.")]
+        dry_run: bool,
+    },
+}
+
+/// Runs the given command; returning its exit code.
+pub(crate) fn run(command: OsString) -> Result<i32> {
+    let cli = Cli::parse_from(
+        [OsString::from("scie-pants"), command]
+            .into_iter()
+            .chain(env::args_os().skip(1)),
+    );
+    match cli.command {
+        Command::BugReport { output } => bug_report::create(output).map(|()| 0),
+        Command::Cache { command } => match command {
+            CacheCommand::List { json } => cache::list(json).map(|()| 0),
+            CacheCommand::Gc {
+                unused_days,
+                dry_run,
+            } => cache::gc(unused_days, dry_run).map(|()| 0),
+        },
+        Command::Init {
+            dir,
+            pants_version,
+            backends,
+            interpreter_constraint,
+            build_root_marker,
+            gitignore,
+            force,
+        } => init::init(InitOptions {
+            dir,
+            pants_version,
+            backends,
+            interpreter_constraint,
+            build_root_marker,
+            gitignore,
+            force,
+        })
+        .map(|()| 0),
+        Command::Prefetch {
+            pants_versions,
+            build_roots,
+            python,
+        } => prefetch::prefetch(PrefetchOptions {
+            pants_versions,
+            build_roots,
+            python,
+        }),
+        Command::RunMany {
+            dirs,
+            globs,
+            jobs,
+            pants_args,
+        } => run_many::run_many(RunManyOptions {
+            dirs,
+            globs,
+            jobs,
+            pants_args,
+        }),
+        Command::SetVersion {
+            pants_version,
+            config,
+            check,
+        } => set_version::set_version(&pants_version, config, check),
+        Command::UpgradePants {
+            to,
+            config,
+            releases_file,
+            dry_run,
+        } => upgrade::upgrade(UpgradeOptions {
+            to,
+            config,
+            releases_file,
+            dry_run,
+        })
+        .map(|()| 0),
+    }
+}
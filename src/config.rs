@@ -1,18 +1,24 @@
 // Copyright 2022 Pants project contributors.
 // Licensed under the Apache License, Version 2.0 (see LICENSE).
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use logging_timer::time;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use toml::Spanned;
 
 use crate::build_root::BuildRoot;
+use crate::diagnostic::TomlDiagnostic;
+use crate::error::{ErrorKind, LauncherError, WithErrorKind};
+use crate::version::Version;
+use crate::{env_flag, version};
 
 #[derive(Default, Deserialize)]
 pub(crate) struct Global {
     #[serde(default)]
-    pub(crate) pants_version: Option<String>,
+    pub(crate) pants_version: Option<Spanned<String>>,
 }
 
 #[derive(Default, Deserialize)]
@@ -25,6 +31,17 @@ pub(crate) struct Default {
     pub(crate) delegate_bootstrap: Option<bool>,
 }
 
+#[derive(Default, Deserialize)]
+pub(crate) struct SciePants {
+    pub(crate) min_version: Option<Spanned<String>>,
+    pub(crate) pants_source: Option<PathBuf>,
+    pub(crate) pants_source_strict_version: Option<bool>,
+    pub(crate) pants_source_args: Option<Vec<String>>,
+    pub(crate) hermetic_env: Option<bool>,
+    pub(crate) hermetic_env_allowlist: Option<Vec<String>>,
+    pub(crate) redact_env_patterns: Option<Vec<String>>,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct Config {
     #[serde(default, rename = "GLOBAL")]
@@ -33,22 +50,33 @@ pub(crate) struct Config {
     pub(crate) debugpy: DebugPy,
     #[serde(default, rename = "DEFAULT")]
     pub(crate) default: Default,
+    #[serde(default, rename = "scie-pants")]
+    pub(crate) scie_pants: SciePants,
 }
 
 pub(crate) struct PantsConfig {
     build_root: BuildRoot,
+    path: PathBuf,
     pub(crate) config: Config,
 }
 
 impl PantsConfig {
     pub(crate) fn package_version(&self) -> Option<String> {
-        self.config.global.pants_version.clone()
+        self.config
+            .global
+            .pants_version
+            .as_ref()
+            .map(|pants_version| pants_version.get_ref().clone())
     }
 
     pub(crate) fn build_root(&self) -> &Path {
         self.build_root.as_path()
     }
 
+    pub(crate) fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
     pub(crate) fn debugpy_version(&self) -> Option<String> {
         self.config.debugpy.version.clone()
     }
@@ -58,26 +86,253 @@ impl PantsConfig {
     }
 }
 
+/// Parses the given TOML file contents, reporting any parse error with a rustc-style diagnostic.
+fn parse_toml<T: DeserializeOwned>(
+    path: &Path,
+    contents: &str,
+    context: impl FnOnce() -> String,
+) -> Result<T> {
+    toml::from_str(contents).map_err(|err: toml::de::Error| {
+        diagnostic_error(
+            ErrorKind::BadConfig,
+            TomlDiagnostic::from_error(path, contents, &err),
+            context(),
+        )
+    })
+}
+
+fn diagnostic_error(kind: ErrorKind, diagnostic: TomlDiagnostic, context: String) -> anyhow::Error {
+    let location = diagnostic.location().clone();
+    LauncherError::new(kind, anyhow::Error::new(diagnostic).context(context))
+        .with_location(Some(location))
+        .into()
+}
+
+/// Fails if this scie-pants is older than the `[scie-pants] min_version` the repo requires unless
+/// the check is bypassed with `PANTS_BOOTSTRAP_SKIP_MIN_VERSION_CHECK`.
+fn check_min_version(
+    path: &Path,
+    contents: &str,
+    min_version: &Spanned<String>,
+    provenance: &str,
+) -> Result<()> {
+    let Some(required) = Version::parse(min_version.get_ref()) else {
+        return Err(diagnostic_error(
+            ErrorKind::BadConfig,
+            TomlDiagnostic::new(
+                path,
+                contents,
+                &format!(
+                    "invalid min_version: {min_version} is not a valid version",
+                    min_version = min_version.get_ref()
+                ),
+                min_version.span(),
+            ),
+            format!(
+                "Invalid Pants config in {path}{provenance}",
+                path = path.display()
+            ),
+        ));
+    };
+    let current = Version::parse(env!("CARGO_PKG_VERSION"))
+        .expect("The scie-pants package version is a valid version.");
+    if current >= required {
+        return Ok(());
+    }
+    if env_flag("PANTS_BOOTSTRAP_SKIP_MIN_VERSION_CHECK").unwrap_or_default() {
+        eprintln!(
+            "WARNING: This repo requires scie-pants >= {required} but this is scie-pants \
+            {current}. Proceeding since PANTS_BOOTSTRAP_SKIP_MIN_VERSION_CHECK is set."
+        );
+        return Ok(());
+    }
+    Err(diagnostic_error(
+        ErrorKind::LauncherTooOld,
+        TomlDiagnostic::new(
+            path,
+            contents,
+            &format!(
+                "this repo requires scie-pants >= {required}, run `SCIE_BOOT=update scie-pants`\n\
+                this is scie-pants {current}; set PANTS_BOOTSTRAP_SKIP_MIN_VERSION_CHECK=1 to \
+                bypass this check"
+            ),
+            min_version.span(),
+        )
+        .without_help(),
+        format!(
+            "Unsupported scie-pants version for {path}{provenance}",
+            path = path.display()
+        ),
+    ))
+}
+
 impl PantsConfig {
-    #[time("debug", "PantsConfig::{}")]
-    pub(crate) fn parse(build_root: BuildRoot) -> Result<PantsConfig> {
-        let (pants_config, provenance) = if let Some(path) = std::env::var_os("PANTS_TOML") {
+    /// Returns the path of the Pants config file for the given build root along with a description
+    /// of how it was selected, if not by default.
+    pub(crate) fn locate(build_root: &BuildRoot) -> (PathBuf, &'static str) {
+        if let Some(path) = std::env::var_os("PANTS_TOML") {
             (path.into(), " (via PANTS_TOML env var)")
         } else {
             (build_root.join("pants.toml"), "")
-        };
-        let contents = std::fs::read_to_string(&pants_config).with_context(|| {
+        }
+    }
+
+    #[time("debug", "PantsConfig::{}")]
+    pub(crate) fn parse(build_root: BuildRoot) -> Result<PantsConfig> {
+        let (pants_config, provenance) = Self::locate(&build_root);
+        let contents = std::fs::read_to_string(&pants_config)
+            .with_context(|| {
+                format!(
+                    "Failed to read Pants config from {path}{provenance}",
+                    path = pants_config.display()
+                )
+            })
+            .error_kind(ErrorKind::BadConfig)?;
+        let config: Config = parse_toml(&pants_config, &contents, || {
             format!(
-                "Failed to read Pants config from {path}{provenance}",
+                "Failed to parse Pants config from {path}{provenance}",
                 path = pants_config.display()
             )
         })?;
-        let config: Config = toml::from_str(&contents).with_context(|| {
+        if let Some(pants_version) = &config.global.pants_version {
+            if let Err(problem) = version::validate_pants_version(pants_version.get_ref()) {
+                return Err(diagnostic_error(
+                    ErrorKind::BadConfig,
+                    TomlDiagnostic::new(
+                        &pants_config,
+                        &contents,
+                        &format!("invalid pants_version: {problem}"),
+                        pants_version.span(),
+                    ),
+                    format!(
+                        "Invalid Pants config in {path}{provenance}",
+                        path = pants_config.display()
+                    ),
+                ));
+            }
+        }
+        if let Some(min_version) = &config.scie_pants.min_version {
+            check_min_version(&pants_config, &contents, min_version, provenance)?;
+        }
+        Ok(PantsConfig {
+            build_root,
+            path: pants_config,
+            config,
+        })
+    }
+
+    /// Like `parse`, but returns `None` instead of failing when the build root is demarcated by a
+    /// `BUILDROOT` or `BUILD_ROOT` marker file and there is no `pants.toml` to parse.
+    pub(crate) fn parse_if_present(build_root: BuildRoot) -> Result<Option<PantsConfig>> {
+        if std::env::var_os("PANTS_TOML").is_none() && !build_root.join("pants.toml").is_file() {
+            return Ok(None);
+        }
+        Self::parse(build_root).map(Some)
+    }
+}
+
+/// Per-user scie-pants configuration that takes precedence over the `[scie-pants]` table in
+/// `pants.toml`.
+pub(crate) struct UserConfig {
+    path: PathBuf,
+    pub(crate) config: SciePants,
+}
+
+impl UserConfig {
+    pub(crate) fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    #[time("debug", "UserConfig::{}")]
+    pub(crate) fn load() -> Result<Option<UserConfig>> {
+        let Some(config_dir) = dirs::config_dir() else {
+            return Ok(None);
+        };
+        let path = config_dir.join("scie-pants").join("config.toml");
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| {
+                format!(
+                    "Failed to read scie-pants user config from {path}",
+                    path = path.display()
+                )
+            })
+            .error_kind(ErrorKind::BadConfig)?;
+        let config: SciePants = parse_toml(&path, &contents, || {
             format!(
-                "Failed to parse Pants config from {path}{provenance}",
-                path = pants_config.display()
+                "Failed to parse scie-pants user config from {path}",
+                path = path.display()
             )
         })?;
-        Ok(PantsConfig { build_root, config })
+        Ok(Some(UserConfig { path, config }))
+    }
+}
+
+/// The env vars always passed through to Pants in hermetic env mode. A trailing `*` matches any
+/// env var name with the given prefix.
+///
+/// Pants is launched via a scie-jump re-exec; so this includes the env vars scie-jump uses to
+/// locate its nce cache and temporary files. Dropping them would silently switch the launch to a
+/// different cache and re-install Pants and CPython there.
+const HERMETIC_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "TERM",
+    "LANG",
+    "LANGUAGE",
+    "LC_*",
+    "PANTS_*",
+    "SCIE*",
+    "XDG_CACHE_HOME",
+    "TMPDIR",
+    "LOCALAPPDATA",
+    "TEMP",
+    "TMP",
+];
+
+/// Returns the allowlist of env vars to pass through to Pants if hermetic env mode is enabled via
+/// the `PANTS_BOOTSTRAP_HERMETIC_ENV` env var or else the user config or `pants.toml`. The builtin
+/// allowlist is extended with the `hermetic_env_allowlist` entries from both configs.
+pub(crate) fn hermetic_env_allowlist(
+    pants_config: Option<&PantsConfig>,
+    user_config: Option<&UserConfig>,
+) -> Option<Vec<String>> {
+    let user_config = user_config.map(|uc| &uc.config);
+    let pants_config = pants_config.map(|pc| &pc.config.scie_pants);
+    let enabled = env_flag("PANTS_BOOTSTRAP_HERMETIC_ENV")
+        .or_else(|| user_config.and_then(|config| config.hermetic_env))
+        .or_else(|| pants_config.and_then(|config| config.hermetic_env))
+        .unwrap_or_default();
+    if !enabled {
+        return None;
+    }
+    let mut allowlist = HERMETIC_ENV_ALLOWLIST
+        .iter()
+        .map(|entry| entry.to_string())
+        .collect::<Vec<_>>();
+    for config in [user_config, pants_config].into_iter().flatten() {
+        if let Some(entries) = &config.hermetic_env_allowlist {
+            allowlist.extend(entries.iter().cloned());
+        }
     }
+    Some(allowlist)
+}
+
+/// Returns the `redact_env_patterns` configured in the user config and `pants.toml`. These extend
+/// the builtin patterns used to mask sensitive env var values in launcher logs.
+pub(crate) fn redact_env_patterns(
+    pants_config: Option<&PantsConfig>,
+    user_config: Option<&UserConfig>,
+) -> Vec<String> {
+    [
+        user_config.map(|uc| &uc.config),
+        pants_config.map(|pc| &pc.config.scie_pants),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|config| config.redact_env_patterns.clone())
+    .flatten()
+    .collect()
 }
@@ -0,0 +1,164 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::error::Location;
+
+/// The config values scie-pants reads along with a description of their expected type for use in
+/// hints.
+const CONFIG_HINTS: &[(&str, &str, &str)] = &[
+    (
+        "GLOBAL",
+        "pants_version",
+        r#"a version string, e.g.: `pants_version = "2.17.0"`"#,
+    ),
+    (
+        "debugpy",
+        "version",
+        r#"a version string, e.g.: `version = "1.6.0"`"#,
+    ),
+    (
+        "DEFAULT",
+        "delegate_bootstrap",
+        "a boolean, e.g.: `delegate_bootstrap = true`",
+    ),
+    (
+        "scie-pants",
+        "min_version",
+        r#"a scie-pants version string, e.g.: `min_version = "0.8.0"`"#,
+    ),
+];
+
+/// A rustc-style rendering of a TOML parse error showing the offending source line.
+#[derive(Debug)]
+pub(crate) struct TomlDiagnostic {
+    location: Location,
+    message: String,
+    source_line: String,
+    span_width: usize,
+    help: Option<String>,
+}
+
+impl TomlDiagnostic {
+    pub(crate) fn from_error(path: &Path, contents: &str, error: &toml::de::Error) -> Self {
+        Self::new(
+            path,
+            contents,
+            error.message(),
+            error.span().unwrap_or(0..0),
+        )
+    }
+
+    /// Creates a diagnostic for a problem with the TOML source at the given byte span.
+    pub(crate) fn new(path: &Path, contents: &str, message: &str, span: Range<usize>) -> Self {
+        let location = Location::from_offset(path, contents, span.start);
+        let source_line = contents
+            .lines()
+            .nth(location.line - 1)
+            .unwrap_or_default()
+            .to_string();
+        let span_width = span_width(contents, span.clone(), &source_line, location.column);
+        let help = table_and_key(contents, span.start).and_then(|(table, key)| {
+            CONFIG_HINTS
+                .iter()
+                .find(|(hint_table, hint_key, _)| *hint_table == table && *hint_key == key)
+                .map(|(table, key, expected)| format!("`[{table}] {key}` should be {expected}"))
+        });
+        Self {
+            location,
+            message: message.trim().to_string(),
+            source_line,
+            span_width,
+            help,
+        }
+    }
+
+    /// Drops the hint about the expected value type for problems with otherwise well-formed values.
+    pub(crate) fn without_help(mut self) -> Self {
+        self.help = None;
+        self
+    }
+
+    pub(crate) fn location(&self) -> &Location {
+        &self.location
+    }
+}
+
+/// The number of characters of the span on its first line, but always at least one.
+fn span_width(contents: &str, span: Range<usize>, source_line: &str, column: usize) -> usize {
+    let span_chars = contents
+        .get(span)
+        .map(|text| text.lines().next().unwrap_or_default().chars().count())
+        .unwrap_or_default();
+    let remaining_chars = source_line.chars().count().saturating_sub(column - 1);
+    span_chars.min(remaining_chars).max(1)
+}
+
+/// Finds the table header and key of the `key = value` line containing the given byte offset.
+fn table_and_key(contents: &str, offset: usize) -> Option<(String, String)> {
+    let preceding = contents.get(..offset)?;
+    let line_start = preceding.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let line = contents[line_start..].lines().next()?;
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim().trim_matches('"').to_string();
+    let table = contents[..line_start]
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| line.starts_with('['))?
+        .trim_matches(|c| c == '[' || c == ']')
+        .trim()
+        .trim_matches('"')
+        .to_string();
+    Some((table, key))
+}
+
+impl Display for TomlDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut message_lines = self.message.lines();
+        writeln!(
+            f,
+            "error: {message}",
+            message = message_lines.next().unwrap_or_default()
+        )?;
+
+        let line_number = self.location.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        writeln!(
+            f,
+            "{gutter}--> {path}:{line}:{column}",
+            path = self.location.path.display(),
+            line = self.location.line,
+            column = self.location.column
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(
+            f,
+            "{line_number} | {source_line}",
+            source_line = self.source_line
+        )?;
+        write!(
+            f,
+            "{gutter} | {padding}{carets}",
+            padding = " ".repeat(self.location.column - 1),
+            carets = "^".repeat(self.span_width)
+        )?;
+
+        let notes = message_lines.collect::<Vec<_>>();
+        if !notes.is_empty() || self.help.is_some() {
+            write!(f, "\n{gutter} |")?;
+        }
+        for note in notes {
+            write!(f, "\n{gutter} = note: {note}")?;
+        }
+        if let Some(help) = &self.help {
+            write!(f, "\n{gutter} = help: {help}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for TomlDiagnostic {}
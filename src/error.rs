@@ -0,0 +1,177 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::fmt::{Debug, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+/// The categories of launcher failures, each with a stable exit code following the sysexits.h
+/// conventions so that callers can tell launcher failures apart from failing Pants runs.
+///
+/// See: https://man.freebsd.org/cgi/man.cgi?query=sysexits
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ErrorKind {
+    /// More than one of the env vars selecting a Pants version were set (EX_USAGE).
+    ConflictingVersions,
+    /// No `pants.toml`, `BUILDROOT` or `BUILD_ROOT` file was found (EX_NOINPUT).
+    NoBuildRoot,
+    /// Launching Pants needs the network but `PANTS_BOOTSTRAP_OFFLINE` is set or the network could
+    /// not be reached (EX_NOHOST).
+    Offline,
+    /// The `.pants.bootstrap` script exited non-zero (EX_UNAVAILABLE).
+    BootstrapScriptFailed,
+    /// Any launcher failure not otherwise categorized (EX_SOFTWARE).
+    Internal,
+    /// Executing the Pants process failed (EX_OSERR).
+    ExecFailed,
+    /// The `SCIE` env var set by the scie-jump was missing (EX_OSFILE).
+    MissingScie,
+    /// The repo requires a newer scie-pants via `[scie-pants] min_version`; updating and retrying
+    /// will fix it (EX_TEMPFAIL).
+    LauncherTooOld,
+    /// The `pants.toml`, the scie-pants user config, the `PANTS_SOURCE` checkout or the
+    /// `PANTS_VERSION` or `PANTS_SHA` env var is invalid (EX_CONFIG).
+    BadConfig,
+}
+
+impl ErrorKind {
+    pub(crate) fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::ConflictingVersions => 64,
+            ErrorKind::NoBuildRoot => 66,
+            ErrorKind::Offline => 68,
+            ErrorKind::BootstrapScriptFailed => 69,
+            ErrorKind::Internal => 70,
+            ErrorKind::ExecFailed => 71,
+            ErrorKind::MissingScie => 72,
+            ErrorKind::LauncherTooOld => 75,
+            ErrorKind::BadConfig => 78,
+        }
+    }
+}
+
+/// A 1-based line and column in a file an error pertains to.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct Location {
+    pub(crate) path: PathBuf,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+impl Location {
+    /// Locates the given byte offset into the contents of the file at the given path.
+    pub(crate) fn from_offset(path: &Path, contents: &str, offset: usize) -> Self {
+        let preceding = &contents[..offset.min(contents.len())];
+        let line_start = preceding.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        Self {
+            path: path.to_path_buf(),
+            line: preceding.matches('\n').count() + 1,
+            column: preceding[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// A launcher error tagged with its `ErrorKind`. It displays as the error it wraps; so tagging an
+/// error does not change how it is reported.
+pub(crate) struct LauncherError {
+    kind: ErrorKind,
+    error: anyhow::Error,
+    location: Option<Location>,
+}
+
+impl LauncherError {
+    pub(crate) fn new(kind: ErrorKind, error: anyhow::Error) -> Self {
+        Self {
+            kind,
+            error,
+            location: None,
+        }
+    }
+
+    pub(crate) fn with_location(mut self, location: Option<Location>) -> Self {
+        self.location = location;
+        self
+    }
+
+    fn find(error: &anyhow::Error) -> Option<&LauncherError> {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<LauncherError>())
+    }
+
+    /// Returns the kind of the outermost `LauncherError` in the error's chain, if any, or else
+    /// `ErrorKind::Internal`.
+    pub(crate) fn kind_of(error: &anyhow::Error) -> ErrorKind {
+        Self::find(error)
+            .map(|launcher_error| launcher_error.kind)
+            .unwrap_or(ErrorKind::Internal)
+    }
+
+    /// Renders the error as a single line JSON object for consumption by tools.
+    pub(crate) fn to_json(error: &anyhow::Error) -> String {
+        #[derive(Serialize)]
+        struct JsonError {
+            code: ErrorKind,
+            exit_code: i32,
+            message: String,
+            context: Vec<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            file: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            line: Option<usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            column: Option<usize>,
+        }
+
+        let kind = Self::kind_of(error);
+        let location =
+            Self::find(error).and_then(|launcher_error| launcher_error.location.as_ref());
+        let json_error = JsonError {
+            code: kind,
+            exit_code: kind.exit_code(),
+            message: error.to_string(),
+            context: error
+                .chain()
+                .skip(1)
+                .map(|cause| cause.to_string())
+                .collect(),
+            file: location.map(|location| location.path.to_string_lossy().into_owned()),
+            line: location.map(|location| location.line),
+            column: location.map(|location| location.column),
+        };
+        serde_json::to_string(&json_error)
+            .expect("Serializing only strings and integers as JSON should never fail.")
+    }
+}
+
+impl Debug for LauncherError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.error, f)
+    }
+}
+
+impl Display for LauncherError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl std::error::Error for LauncherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+pub(crate) trait WithErrorKind<T> {
+    /// Tags the error, if any, with the given kind.
+    fn error_kind(self, kind: ErrorKind) -> Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> WithErrorKind<T> for Result<T, E> {
+    fn error_kind(self, kind: ErrorKind) -> Result<T> {
+        self.map_err(|error| LauncherError::new(kind, error.into()).into())
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
+
+use crate::error::{ErrorKind, WithErrorKind};
+use crate::releases;
+use crate::version::{self, VersionAlias};
+
+/// The entries Pants recommends ignoring; see: https://www.pantsbuild.org/docs/initial-configuration
+const GITIGNORE_ENTRIES: &[&str] = &[".pants.d/", "dist/", ".pids/", ".pants.workdir.file_lock*"];
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, ValueEnum)]
+pub(crate) enum Backend {
+    Python,
+    Go,
+    Shell,
+    Docker,
+}
+
+impl Backend {
+    fn package(self) -> &'static str {
+        match self {
+            Backend::Python => "pants.backend.python",
+            Backend::Go => "pants.backend.experimental.go",
+            Backend::Shell => "pants.backend.shell",
+            Backend::Docker => "pants.backend.docker",
+        }
+    }
+}
+
+pub(crate) struct InitOptions {
+    pub(crate) dir: Option<PathBuf>,
+    pub(crate) pants_version: Option<String>,
+    pub(crate) backends: Vec<Backend>,
+    pub(crate) interpreter_constraint: Option<String>,
+    pub(crate) build_root_marker: bool,
+    pub(crate) gitignore: bool,
+    pub(crate) force: bool,
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+fn render_pants_toml(
+    pants_version: &str,
+    backends: &[Backend],
+    interpreter_constraint: Option<&str>,
+) -> String {
+    let mut pants_toml = String::new();
+    // N.B.: Writing to a String never fails.
+    let _ = writeln!(
+        pants_toml,
+        "# Pants configuration. See: https://www.pantsbuild.org/docs/reference-all-subsystems\n\
+        \n\
+        [GLOBAL]\n\
+        # The Pants version to run. To upgrade Pants, change this and see:\n\
+        #   https://www.pantsbuild.org/docs/upgrade-tips\n\
+        pants_version = {pants_version}\n\
+        \n\
+        # The backends that provide Pants' support for your languages and tools. See:\n\
+        #   https://www.pantsbuild.org/docs/enabling-backends\n\
+        backend_packages = [{packages}]",
+        pants_version = toml_string(pants_version),
+        packages = backends
+            .iter()
+            .map(|backend| format!("\n  {package},", package = toml_string(backend.package())))
+            .chain((!backends.is_empty()).then(|| "\n".to_string()))
+            .collect::<String>()
+    );
+    if let Some(interpreter_constraint) = interpreter_constraint {
+        let _ = writeln!(
+            pants_toml,
+            "\n\
+            [python]\n\
+            # The Python interpreters your code is compatible with. See:\n\
+            #   https://www.pantsbuild.org/docs/python-interpreter-compatibility\n\
+            interpreter_constraints = [{interpreter_constraint}]",
+            interpreter_constraint = toml_string(interpreter_constraint)
+        );
+    }
+    pants_toml
+}
+
+/// Returns the `.gitignore` contents with any missing Pants entries appended, or `None` if all the
+/// entries are already present.
+fn gitignore_with_pants_entries(existing: &str) -> Option<String> {
+    let missing = GITIGNORE_ENTRIES
+        .iter()
+        .filter(|entry| !existing.lines().any(|line| line.trim() == **entry))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return None;
+    }
+    let mut gitignore = existing.to_string();
+    if !gitignore.is_empty() && !gitignore.ends_with('\n') {
+        gitignore.push('\n');
+    }
+    gitignore.push_str("# Pants workspace files\n");
+    for entry in missing {
+        gitignore.push_str(entry);
+        gitignore.push('\n');
+    }
+    Some(gitignore)
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    std::fs::write(path, contents)
+        .with_context(|| format!("Failed to write {path}", path = path.display()))?;
+    println!("Wrote {path}", path = path.display());
+    Ok(())
+}
+
+/// Scaffolds a new Pants project.
+pub(crate) fn init(options: InitOptions) -> Result<()> {
+    let dir = match options.dir {
+        Some(dir) => dir,
+        None => std::env::current_dir().context("Failed to determine the current directory.")?,
+    };
+    if options.interpreter_constraint.is_some() && !options.backends.contains(&Backend::Python) {
+        return Err(anyhow!(
+            "An interpreter constraint only applies to the python backend; add `--backend python`."
+        ))
+        .error_kind(ErrorKind::BadConfig);
+    }
+
+    let pants_toml = dir.join("pants.toml");
+    let build_root_marker = dir.join("BUILD_ROOT");
+    let mut files = vec![&pants_toml];
+    if options.build_root_marker {
+        files.push(&build_root_marker);
+    }
+    if !options.force {
+        let existing = files
+            .iter()
+            .filter(|file| file.exists())
+            .map(|file| file.display().to_string())
+            .collect::<Vec<_>>();
+        if !existing.is_empty() {
+            bail!(
+                "Refusing to overwrite {existing}. Re-run with --force to overwrite.",
+                existing = existing.join(" and ")
+            );
+        }
+    }
+
+    let pants_version = match options.pants_version {
+        Some(pants_version) => {
+            version::validate_pants_version(&pants_version)
+                .map_err(|problem| anyhow!("Invalid --pants-version: {problem}"))
+                .error_kind(ErrorKind::BadConfig)?;
+            match VersionAlias::parse(&pants_version) {
+                Some(alias) => releases::resolve(&alias)?,
+                None => pants_version,
+            }
+        }
+        None => releases::resolve(&VersionAlias::LatestStable)?,
+    };
+
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {dir}", dir = dir.display()))?;
+    let mut backends = options.backends;
+    backends.sort();
+    backends.dedup();
+    write(
+        &pants_toml,
+        &render_pants_toml(
+            &pants_version,
+            &backends,
+            options.interpreter_constraint.as_deref(),
+        ),
+    )?;
+    if options.build_root_marker {
+        write(&build_root_marker, "")?;
+    }
+    if options.gitignore {
+        let gitignore = dir.join(".gitignore");
+        let existing = match std::fs::read_to_string(&gitignore) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to read {path}", path = gitignore.display()))
+            }
+        };
+        if let Some(contents) = gitignore_with_pants_entries(&existing) {
+            write(&gitignore, &contents)?;
+        }
+    }
+    Ok(())
+}
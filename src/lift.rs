@@ -0,0 +1,59 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::env;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// A file packaged in the scie as described by its lift manifest.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct File {
+    pub(crate) name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hash: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Lift {
+    files: Vec<File>,
+}
+
+#[derive(Deserialize)]
+struct Scie {
+    lift: Lift,
+}
+
+#[derive(Deserialize)]
+struct LiftManifest {
+    scie: Scie,
+}
+
+/// Retrieves the lift manifest of the scie scie-pants is running from as JSON bytes.
+pub(crate) fn manifest() -> Result<Vec<u8>> {
+    let scie =
+        env::var_os("SCIE").context("Failed to retrieve SCIE location from the environment.")?;
+    let output = Command::new(&scie)
+        .env("SCIE", "inspect")
+        .output()
+        .with_context(|| format!("Failed to spawn {scie:?} to inspect its lift manifest."))?;
+    if !output.status.success() {
+        bail!(
+            "Inspecting the lift manifest of {scie:?} failed: {stderr}",
+            stderr = String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Retrieves the files packaged in the scie scie-pants is running from.
+pub(crate) fn files() -> Result<Vec<File>> {
+    let manifest: LiftManifest =
+        serde_json::from_slice(&manifest()?).context("Failed to parse the lift manifest.")?;
+    Ok(manifest.scie.lift.files)
+}
@@ -3,86 +3,82 @@
 
 use std::env;
 use std::ffi::{OsStr, OsString};
-use std::fmt::Debug;
 use std::path::PathBuf;
 
-use anyhow::{anyhow, bail, Context, Result};
+use anyhow::{anyhow, Context, Result};
 use build_root::BuildRoot;
-use log::{info, trace};
+use is_terminal::IsTerminal;
+use log::{debug, info, trace};
 use logging_timer::{time, timer, Level};
 use uuid::Uuid;
 
-use crate::config::PantsConfig;
+use crate::config::{hermetic_env_allowlist, redact_env_patterns, PantsConfig, UserConfig};
+use crate::error::{ErrorKind, LauncherError, WithErrorKind};
+use crate::pants_source::get_pants_from_sources_process;
+use crate::process::{EnvOp, Process};
+use crate::version::VersionAlias;
 
+mod bug_report;
 mod build_root;
+mod cache;
+mod commands;
 mod config;
-
-#[derive(Debug, Default)]
-struct Process {
-    exe: OsString,
-    args: Vec<OsString>,
-    env: Vec<(OsString, OsString)>,
-}
-
-impl Process {
-    #[cfg(windows)]
-    fn exec(self) -> Result<i32> {
-        use std::process::Command;
-
-        let exit_status = Command::new(&self.exe)
-            .args(&self.args)
-            .args(env::args().skip(1))
-            .envs(self.env.clone())
-            .spawn()?
-            .wait()
-            .with_context(|| format!("Failed to execute process: {self:#?}"))?;
-        Ok(exit_status
-            .code()
-            .unwrap_or_else(|| if exit_status.success() { 0 } else { 1 }))
-    }
-
-    #[cfg(unix)]
-    fn exec(self) -> Result<i32> {
-        use std::ffi::CString;
-        use std::os::unix::ffi::OsStringExt;
-
-        use nix::unistd::execv;
-
-        let c_exe = CString::new(self.exe.into_vec())
-            .context("Failed to convert executable to a C string.")?;
-
-        let mut c_args = vec![c_exe.clone()];
-        c_args.extend(
-            self.args
-                .into_iter()
-                .chain(env::args().skip(1).map(OsString::from))
-                .map(|arg| {
-                    CString::new(arg.into_vec())
-                        .context("Failed to convert argument to a C string.")
-                })
-                .collect::<Result<Vec<_>, _>>()?,
-        );
-
-        for (name, value) in self.env {
-            env::set_var(name, value);
-        }
-
-        execv(&c_exe, &c_args)
-            .map(|_| 0)
-            .context("Failed to exec process.")
-    }
-}
-
-fn env_version(env_var_name: &str) -> Result<Option<String>> {
+mod diagnostic;
+mod error;
+mod init;
+mod lift;
+mod pants_source;
+mod prefetch;
+mod process;
+mod releases;
+mod run_many;
+mod self_report;
+mod set_version;
+mod upgrade;
+mod version;
+
+fn env_version(
+    env_var_name: &str,
+    validate: fn(&str) -> std::result::Result<(), String>,
+) -> Result<Option<String>> {
     if let Some(raw_version) = env::var_os(env_var_name) {
-        Ok(Some(raw_version.into_string().map_err(|raw| {
+        let version = raw_version.into_string().map_err(|raw| {
             anyhow!("Failed to interpret {env_var_name} {raw:?} as UTF-8 string.")
-        })?))
+        })?;
+        validate(&version)
+            .map_err(|problem| anyhow!("Invalid {env_var_name}: {problem}"))
+            .error_kind(ErrorKind::BadConfig)?;
+        Ok(Some(version))
     } else {
         Ok(None)
     }
 }
 
+/// Interprets an env var as a boolean flag, returning `None` if it is not set. Any value other than
+/// the empty string, `0`, `false`, `no` or `off` (case-insensitive) turns the flag on.
+pub(crate) fn env_flag(env_var_name: &str) -> Option<bool> {
+    env::var_os(env_var_name).map(|value| {
+        !matches!(
+            value.to_string_lossy().trim().to_ascii_lowercase().as_str(),
+            "" | "0" | "false" | "no" | "off"
+        )
+    })
+}
+
+/// Determines whether the launcher must not prompt the user, returning the reason if so.
+///
+/// An explicit `PANTS_BOOTSTRAP_NONINTERACTIVE` setting wins; otherwise prompting is disabled in CI
+/// and when stdin is not a terminal since a prompt would hang or fail there.
+fn noninteractive_reason() -> Option<&'static str> {
+    match env_flag("PANTS_BOOTSTRAP_NONINTERACTIVE") {
+        Some(true) => Some("PANTS_BOOTSTRAP_NONINTERACTIVE is set"),
+        Some(false) => None,
+        None if env_flag("CI").unwrap_or_default() => Some("the CI env var is set"),
+        None if !std::io::stdin().is_terminal() => Some("stdin is not a terminal"),
+        None => None,
+    }
+}
+
 fn find_pants_installation() -> Result<Option<PantsConfig>> {
     if let Ok(build_root) = BuildRoot::find(None) {
         let pants_config = PantsConfig::parse(build_root)?;
@@ -108,53 +104,41 @@ impl ScieBoot {
         .into()
     }
 
-    #[cfg(unix)]
-    fn quote<T: Into<OsString> + Debug>(value: T) -> Result<String> {
-        String::from_utf8(shell_quote::bash::escape(value))
-            .context("Shell-quoted value could not be interpreted as UTF-8.")
-    }
-
-    #[cfg(windows)]
-    fn quote<T: Into<OsString> + Debug>(_value: T) -> Result<String> {
-        // The shell_quote crate assumes unix and fails to compile on Windows.
-        todo!("TODO(John Sirois): Figure out Git bash? shell quoting for Windows WTF-16 strings.")
-    }
-
     fn into_process(
         self,
         scie: String,
         build_root: Option<PathBuf>,
-        env: Vec<(OsString, OsString)>,
+        env: Vec<EnvOp>,
     ) -> Result<Process> {
-        Ok(match build_root.map(|br| br.join(".pants.bootstrap")) {
-            Some(pants_bootstrap) if self != Self::BootstrapTools && pants_bootstrap.is_file() => {
-                Process {
-                    exe: "/usr/bin/env".into(),
-                    args: vec![
-                        "bash".into(),
-                        "-c".into(),
-                        format!(
-                            r#"set -eou pipefail; source {bootstrap}; exec {scie} "$0" "$@""#,
-                            bootstrap = Self::quote(pants_bootstrap)?,
-                            scie = Self::quote(scie)?
-                        )
-                        .into(),
-                    ],
-                    env,
-                }
+        let process = Process {
+            exe: scie.into(),
+            env,
+            ..Default::default()
+        };
+        match build_root {
+            Some(build_root) if self != Self::BootstrapTools => {
+                process.sourcing_pants_bootstrap(&build_root)
             }
-            _ => Process {
-                exe: scie.into(),
-                env,
-                ..Default::default()
-            },
-        })
+            _ => Ok(process),
+        }
     }
 }
 
+/// Whether the launch process is computed to execute it or just to describe it.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub(crate) enum LaunchMode {
+    Exec,
+    /// Computes the launch process without side effects: Pants use is not recorded and version
+    /// aliases are only resolved from the cache.
+    Plan,
+}
+
 #[time("debug", "scie-pants::{}")]
-fn get_pants_process() -> Result<Process> {
+fn get_pants_process(invoked_as_version: Option<String>, mode: LaunchMode) -> Result<Process> {
     let pants_installation = find_pants_installation()?;
+    let user_config = UserConfig::load()?;
+    let env_allowlist = hermetic_env_allowlist(pants_installation.as_ref(), user_config.as_ref());
+    let redact_patterns = redact_env_patterns(pants_installation.as_ref(), user_config.as_ref());
     let (build_root, configured_pants_version, debugpy_version, delegate_bootstrap) =
         if let Some(ref pants_config) = pants_installation {
             (
@@ -167,13 +151,18 @@ fn get_pants_process() -> Result<Process> {
             (None, None, None, false)
         };
 
-    let env_pants_sha = env_version("PANTS_SHA")?;
-    let env_pants_version = env_version("PANTS_VERSION")?;
+    let env_pants_sha = env_version("PANTS_SHA", version::validate_pants_sha)?;
+    // Being invoked as `pants-<version>` or `pants@<version>` acts as `PANTS_VERSION=<version>`.
+    let env_pants_version = match invoked_as_version {
+        Some(version) => Some(version),
+        None => env_version("PANTS_VERSION", version::validate_pants_version)?,
+    };
     if let (Some(pants_sha), Some(pants_version)) = (&env_pants_sha, &env_pants_version) {
-        bail!(
+        return Err(anyhow!(
             "Both PANTS_SHA={pants_sha} and PANTS_VERSION={pants_version} were set. \
             Please choose one.",
-        )
+        ))
+        .error_kind(ErrorKind::ConflictingVersions);
     }
 
     let pants_version = if let Some(env_version) = env_pants_version {
@@ -183,6 +172,20 @@ fn get_pants_process() -> Result<Process> {
     } else {
         None
     };
+    let pants_version = match pants_version {
+        Some(version) => match VersionAlias::parse(&version) {
+            Some(alias) if mode == LaunchMode::Plan => {
+                Some(releases::cached_resolution(&alias).unwrap_or(version))
+            }
+            Some(alias) => {
+                let resolved = releases::resolve(&alias)?;
+                info!("Resolved Pants version {alias} to {resolved}");
+                Some(resolved)
+            }
+            None => Some(version),
+        },
+        None => None,
+    };
 
     if delegate_bootstrap && pants_version.is_none() {
         let exe = build_root
@@ -192,14 +195,43 @@ fn get_pants_process() -> Result<Process> {
         return Ok(Process {
             exe,
             ..Default::default()
-        });
+        }
+        .with_env_allowlist(env_allowlist)
+        .redacting(redact_patterns));
+    }
+
+    if pants_version.is_none() && env_pants_sha.is_none() {
+        if let Some(reason) = noninteractive_reason() {
+            return match pants_installation {
+                Some(ref pants_config) => Err(anyhow!(
+                    "No Pants version is configured and scie-pants will not prompt for one since \
+                    {reason}.\n\
+                    Set `pants_version` in the `[GLOBAL]` table of {pants_toml}, e.g.:\n\
+                    [GLOBAL]\n\
+                    pants_version = \"<version>\"\n\
+                    Or select a version for this run with the PANTS_VERSION env var.",
+                    pants_toml = pants_config.path().display()
+                ))
+                .error_kind(ErrorKind::BadConfig),
+                None => Err(anyhow!(
+                    "No Pants build root was found and scie-pants will not prompt to create one \
+                    since {reason}.\n\
+                    Create a pants.toml at the root of your repo that sets `pants_version` in its \
+                    `[GLOBAL]` table, e.g.:\n\
+                    [GLOBAL]\n\
+                    pants_version = \"<version>\""
+                ))
+                .error_kind(ErrorKind::NoBuildRoot),
+            };
+        }
     }
 
     info!("Found Pants build root at {build_root:?}");
     info!("The required Pants version is {pants_version:?}");
 
-    let scie =
-        env::var("SCIE").context("Failed to retrieve SCIE location from the environment.")?;
+    let scie = env::var("SCIE")
+        .context("Failed to retrieve SCIE location from the environment.")
+        .error_kind(ErrorKind::MissingScie)?;
 
     let pants_debug = matches!(env::var_os("PANTS_DEBUG"), Some(value) if !value.is_empty());
     let scie_boot = match env::var_os("PANTS_BOOTSTRAP_TOOLS") {
@@ -208,23 +240,65 @@ fn get_pants_process() -> Result<Process> {
         None => ScieBoot::Pants,
     };
 
+    if env_flag("PANTS_BOOTSTRAP_OFFLINE").unwrap_or_default() {
+        let offline_version = match (&pants_version, &env_pants_sha) {
+            (Some(version), _) => version.clone(),
+            (None, Some(sha)) => cache::sha_pants_version(sha)?
+                .ok_or_else(|| {
+                    anyhow!(
+                        "PANTS_BOOTSTRAP_OFFLINE is set but Pants for PANTS_SHA={sha} is not \
+                        installed in the scie-pants cache.\n\
+                        Run Pants with the same PANTS_SHA while online to install it."
+                    )
+                })
+                .error_kind(ErrorKind::Offline)?,
+            (None, None) => {
+                return Err(anyhow!(
+                    "PANTS_BOOTSTRAP_OFFLINE is set but the latest stable Pants version to \
+                    configure must be determined over the network."
+                ))
+                .error_kind(ErrorKind::Offline);
+            }
+        };
+        // N.B.: This mirrors the install binding's default debugpy requirement.
+        let debugpy_requirement = (scie_boot == ScieBoot::PantsDebug).then(|| {
+            debugpy_version
+                .clone()
+                .or_else(|| env::var("PANTS_DEBUGPY_VERSION").ok())
+                .filter(|requirement| !requirement.is_empty())
+                .unwrap_or_else(|| "debugpy==1.6.0".to_string())
+        });
+        cache::check_offline(
+            &offline_version,
+            debugpy_requirement.as_deref(),
+            scie_boot != ScieBoot::BootstrapTools,
+        )?;
+    }
+
     let pants_bin_name = env::var_os("PANTS_BIN_NAME")
         .or_else(|| env::var_os("SCIE_ARGV0"))
         .unwrap_or_else(|| scie.clone().into());
 
     let mut env = vec![
-        ("SCIE_BOOT".into(), scie_boot.env_value()),
-        ("PANTS_BIN_NAME".into(), pants_bin_name),
-        (
+        EnvOp::Set("SCIE_BOOT".into(), scie_boot.env_value()),
+        EnvOp::Set("PANTS_BIN_NAME".into(), pants_bin_name),
+        EnvOp::Set(
             "PANTS_DEBUG".into(),
             if pants_debug { "1" } else { "" }.into(),
         ),
+        // A PEX_ROOT or VIRTUAL_ENV leaked from the user's environment can only confuse the
+        // configure and install bindings, which manage their own.
+        EnvOp::Unset("PEX_ROOT".into()),
+        EnvOp::Unset("VIRTUAL_ENV".into()),
     ];
     if let Some(debugpy_version) = debugpy_version {
-        env.push(("PANTS_DEBUGPY_VERSION".into(), debugpy_version.into()));
+        env.push(EnvOp::Set(
+            "PANTS_DEBUGPY_VERSION".into(),
+            debugpy_version.into(),
+        ));
     }
     if let Some(ref build_root) = build_root {
-        env.push((
+        env.push(EnvOp::Set(
             "PANTS_BUILDROOT_OVERRIDE".into(),
             build_root.as_os_str().to_os_string(),
         ));
@@ -232,61 +306,38 @@ fn get_pants_process() -> Result<Process> {
         // by the configure binding, and scie-jump would be smart enough to skip the configure
         // binding when the install binding is a cache hit.
         if configured_pants_version.is_none() {
-            env.push((
+            env.push(EnvOp::Set(
                 "PANTS_TOML".into(),
                 build_root.join("pants.toml").into_os_string(),
             ));
         }
     }
     if let Some(version) = pants_version {
+        if mode == LaunchMode::Exec {
+            if let Err(err) = cache::record_use(&version, build_root.as_deref()) {
+                debug!("Failed to record the use of Pants {version}: {err:#}");
+            }
+        }
         if delegate_bootstrap {
-            env.push(("_PANTS_OVERRIDE_VERSION".into(), version.clone().into()));
+            env.push(EnvOp::Set(
+                "_PANTS_OVERRIDE_VERSION".into(),
+                version.clone().into(),
+            ));
         }
-        env.push(("PANTS_VERSION".into(), version.into()));
+        env.push(EnvOp::Set("PANTS_VERSION".into(), version.into()));
     } else if env_pants_sha.is_none() {
         // Ensure the install binding always re-runs when no Pants version is found so that the
         // the user can be prompted with configuration options.
-        env.push((
+        env.push(EnvOp::Set(
             "PANTS_VERSION_PROMPT_SALT".into(),
             Uuid::new_v4().simple().to_string().into(),
         ))
     }
 
-    scie_boot.into_process(scie, build_root, env)
-}
-
-fn get_pants_from_sources_process(pants_repo_location: PathBuf) -> Result<Process> {
-    let exe = pants_repo_location.join("pants").into_os_string();
-
-    let args = vec!["--no-verify-config".into()];
-
-    let version = std::fs::read_to_string(
-        pants_repo_location
-            .join("src")
-            .join("python")
-            .join("pants")
-            .join("VERSION"),
-    )?;
-
-    // The ENABLE_PANTSD env var is a custom env var defined by the legacy `./pants_from_sources`
-    // script. We maintain support here in perpetuity because it's cheap and we don't break folks'
-    // workflows.
-    let enable_pantsd = env::var_os("ENABLE_PANTSD")
-        .or_else(|| env::var_os("PANTS_PANTSD"))
-        .unwrap_or_else(|| "false".into());
-
-    let build_root = BuildRoot::find(None)?;
-    let env = vec![
-        ("PANTS_VERSION".into(), version.trim().into()),
-        ("PANTS_PANTSD".into(), enable_pantsd),
-        (
-            "PANTS_BUILDROOT_OVERRIDE".into(),
-            build_root.as_os_str().to_os_string(),
-        ),
-        ("no_proxy".into(), "*".into()),
-    ];
-
-    Ok(Process { exe, args, env })
+    Ok(scie_boot
+        .into_process(scie, build_root, env)?
+        .with_env_allowlist(env_allowlist)
+        .redacting(redact_patterns))
 }
 
 fn invoked_as_basename() -> Option<String> {
@@ -302,29 +353,86 @@ fn invoked_as_basename() -> Option<String> {
     basename.map(str::to_owned)
 }
 
-fn main() -> Result<()> {
-    env_logger::init();
+/// Extracts the Pants version from a `pants-<version>` or `pants@<version>` binary name.
+fn version_from_basename(basename: &str) -> Option<String> {
+    let version = basename
+        .strip_prefix("pants-")
+        .or_else(|| basename.strip_prefix("pants@"))?;
+    match version::validate_pants_version(version) {
+        Ok(()) => Some(version.to_owned()),
+        Err(problem) => {
+            debug!("Ignoring the suffix of the binary name {basename}: {problem}");
+            None
+        }
+    }
+}
+
+/// Determines how to launch Pants based on the environment and the name scie-pants was invoked as.
+pub(crate) fn get_launch_process(mode: LaunchMode) -> Result<Process> {
+    let basename = invoked_as_basename();
+    if let Ok(value) = env::var("PANTS_SOURCE") {
+        get_pants_from_sources_process(Some(value))
+    } else if let Some("pants_from_sources") = basename.as_deref() {
+        get_pants_from_sources_process(None)
+    } else {
+        get_pants_process(basename.as_deref().and_then(version_from_basename), mode)
+    }
+}
+
+fn run() -> Result<i32> {
     let _timer = timer!(Level::Debug; "MAIN");
 
     // N.B.: The bogus version of `report` is used to signal scie-pants should report version
     // information for the update tool to use in determining if there are newer versions of
     // scie-pants available.
     if let Ok(value) = env::var("PANTS_BOOTSTRAP_VERSION") {
-        if "report" == value.as_str() {
-            println!(env!("CARGO_PKG_VERSION"));
-            std::process::exit(0);
+        match value.as_str() {
+            "report" => {
+                println!(env!("CARGO_PKG_VERSION"));
+                return Ok(0);
+            }
+            // A structured counterpart to `report` for inventory tooling.
+            "report-json" => return self_report::print().map(|()| 0),
+            _ => {}
         }
     }
 
-    let pants_process = if let Ok(value) = env::var("PANTS_SOURCE") {
-        get_pants_from_sources_process(PathBuf::from(value))
-    } else if let Some("pants_from_sources") = invoked_as_basename().as_deref() {
-        get_pants_from_sources_process(PathBuf::from("..").join("pants"))
-    } else {
-        get_pants_process()
-    }?;
+    // N.B.: This is set by the lift manifest boot commands for the commands scie-pants implements
+    // itself.
+    if let Some(command) = env::var_os("SCIE_PANTS_COMMAND") {
+        return commands::run(command);
+    }
+
+    let pants_process = get_launch_process(LaunchMode::Exec)?;
 
     trace!("Launching: {pants_process:#?}");
-    let exit_code = pants_process.exec()?;
+    // N.B.: This allows inspecting exactly what would be launched without launching it.
+    if let Ok(value) = env::var("PANTS_BOOTSTRAP_LAUNCH_PLAN") {
+        if "json" == value.as_str() {
+            println!(
+                "{launch_plan}",
+                launch_plan = serde_json::to_string_pretty(&pants_process)
+                    .context("Failed to serialize the launch plan as JSON.")?
+            );
+            return Ok(0);
+        }
+    }
+    pants_process.exec().error_kind(ErrorKind::ExecFailed)
+}
+
+fn main() {
+    env_logger::init();
+    let exit_code = match run() {
+        Ok(exit_code) => exit_code,
+        Err(err) => {
+            if let Ok("json") = env::var("SCIE_PANTS_ERROR_FORMAT").as_deref() {
+                eprintln!("{json}", json = LauncherError::to_json(&err));
+            } else {
+                // N.B.: This matches how an error returned from `main` is reported.
+                eprintln!("Error: {err:?}");
+            }
+            LauncherError::kind_of(&err).exit_code()
+        }
+    };
     std::process::exit(exit_code)
 }
@@ -0,0 +1,331 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::env;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use log::debug;
+use regex::Regex;
+
+use crate::build_root::BuildRoot;
+use crate::config::{hermetic_env_allowlist, redact_env_patterns, PantsConfig, UserConfig};
+use crate::error::{ErrorKind, WithErrorKind};
+use crate::process::{EnvOp, Process};
+use crate::{env_flag, version};
+
+pub(crate) enum Provenance {
+    EnvVar,
+    PantsToml(PathBuf),
+    UserConfig(PathBuf),
+    PantsFromSources,
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Provenance::EnvVar => write!(f, "set via the PANTS_SOURCE env var"),
+            Provenance::PantsToml(path) => write!(
+                f,
+                "configured via [scie-pants] pants_source in {path}",
+                path = path.display()
+            ),
+            Provenance::UserConfig(path) => write!(
+                f,
+                "configured via pants_source in {path}",
+                path = path.display()
+            ),
+            Provenance::PantsFromSources => write!(
+                f,
+                "defaulted to ../pants relative to the build root since scie-pants was invoked \
+                as pants_from_sources"
+            ),
+        }
+    }
+}
+
+pub(crate) struct PantsSource {
+    path: PathBuf,
+    provenance: Provenance,
+}
+
+impl PantsSource {
+    /// Determines the Pants checkout to use. The `PANTS_SOURCE` env var has top precedence and is
+    /// interpreted relative to the CWD just like any other path passed on the command line. Failing
+    /// that, a `pants_source` configured in the user config or else the `[scie-pants]` table of
+    /// `pants.toml` is used, and, finally, we fall back to a `pants` sibling of the build root.
+    /// Configured paths are relative to the build root.
+    fn resolve(
+        env_pants_source: Option<String>,
+        build_root: &Path,
+        pants_config: Option<&PantsConfig>,
+        user_config: Option<&UserConfig>,
+    ) -> Self {
+        if let Some(value) = env_pants_source {
+            return Self {
+                path: PathBuf::from(value),
+                provenance: Provenance::EnvVar,
+            };
+        }
+        let configured = user_config
+            .and_then(|uc| {
+                uc.config
+                    .pants_source
+                    .as_ref()
+                    .map(|path| (path, Provenance::UserConfig(uc.path().to_path_buf())))
+            })
+            .or_else(|| {
+                pants_config.and_then(|pc| {
+                    pc.config
+                        .scie_pants
+                        .pants_source
+                        .as_ref()
+                        .map(|path| (path, Provenance::PantsToml(pc.path().to_path_buf())))
+                })
+            });
+        if let Some((path, provenance)) = configured {
+            return Self {
+                path: build_root.join(path),
+                provenance,
+            };
+        }
+        Self {
+            path: build_root.join("..").join("pants"),
+            provenance: Provenance::PantsFromSources,
+        }
+    }
+
+    fn pants_script(&self) -> PathBuf {
+        self.path.join("pants")
+    }
+
+    fn version_file(&self) -> PathBuf {
+        self.path
+            .join("src")
+            .join("python")
+            .join("pants")
+            .join("VERSION")
+    }
+
+    fn validate(&self, build_root: &Path) -> Result<()> {
+        let path = self.path.display();
+        let provenance = &self.provenance;
+        if !self.path.is_dir() {
+            bail!(
+                "The Pants source directory {path} ({provenance}) does not exist.\n\
+                It should be a local clone of https://github.com/pantsbuild/pants."
+            );
+        }
+        let missing = [self.pants_script(), self.version_file()]
+            .into_iter()
+            .filter(|file| !file.is_file())
+            .map(|file| format!("  {file}", file = file.display()))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            bail!(
+                "The Pants source directory {path} ({provenance}) does not look like a Pants \
+                checkout. The following expected files are missing:\n{missing}",
+                missing = missing.join("\n")
+            );
+        }
+        if same_dir(&self.path, build_root)? {
+            bail!(
+                "The Pants source directory {path} ({provenance}) is the current build root.\n\
+                To run Pants from sources in the Pants repo itself, use its ./pants script \
+                directly."
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Checks the Pants version of the checkout against the `pants_version` the repo is pinned to. A
+/// major or minor version mismatch is a warning by default and an error in strict mode.
+fn check_version(
+    pants_source: &PantsSource,
+    source_version: &str,
+    pants_config: Option<&PantsConfig>,
+    user_config: Option<&UserConfig>,
+) -> Result<()> {
+    let Some(pants_config) = pants_config else {
+        return Ok(());
+    };
+    let Some(configured_version) = pants_config.package_version() else {
+        return Ok(());
+    };
+    let (Some(source_major_minor), Some(configured_major_minor)) = (
+        version::major_minor(source_version),
+        version::major_minor(&configured_version),
+    ) else {
+        debug!(
+            "Skipping version check of Pants source version {source_version} against configured \
+            version {configured_version} since one or both are not PEP 440 versions."
+        );
+        return Ok(());
+    };
+    if source_major_minor == configured_major_minor {
+        return Ok(());
+    }
+
+    let strict = env_flag("PANTS_BOOTSTRAP_SOURCE_STRICT_VERSION")
+        .or_else(|| user_config.and_then(|uc| uc.config.pants_source_strict_version))
+        .or(pants_config.config.scie_pants.pants_source_strict_version)
+        .unwrap_or_default();
+    let message = format!(
+        "The Pants source directory {path} ({provenance}) is at version {source_version}, but \
+        {pants_toml} pins pants_version = \"{configured_version}\" ({source_major}.{source_minor} \
+        vs {configured_major}.{configured_minor}).",
+        path = pants_source.path.display(),
+        provenance = pants_source.provenance,
+        pants_toml = pants_config.path().display(),
+        source_major = source_major_minor.0,
+        source_minor = source_major_minor.1,
+        configured_major = configured_major_minor.0,
+        configured_minor = configured_major_minor.1,
+    );
+    if strict {
+        bail!(
+            "{message}\n\
+            To downgrade this error to a warning, set PANTS_BOOTSTRAP_SOURCE_STRICT_VERSION=0."
+        );
+    }
+    eprintln!(
+        "WARNING: {message}\n\
+        Set PANTS_BOOTSTRAP_SOURCE_STRICT_VERSION=1 to make this an error."
+    );
+    Ok(())
+}
+
+/// Determines whether the checkout's `./pants` script reads the `PANTS_DEBUG` env var. Only a shell
+/// expansion of the variable outside of a comment counts.
+fn supports_pants_debug(pants_source: &PantsSource) -> Result<bool> {
+    let pants_script = pants_source.pants_script();
+    let contents = std::fs::read_to_string(&pants_script).with_context(|| {
+        format!(
+            "Failed to read {pants_script}",
+            pants_script = pants_script.display()
+        )
+    })?;
+    let expansion = Regex::new(r"\$\{?PANTS_DEBUG\b")?;
+    Ok(contents
+        .lines()
+        .map(strip_shell_comment)
+        .any(|code| expansion.is_match(code)))
+}
+
+fn strip_shell_comment(line: &str) -> &str {
+    // N.B.: A `#` only starts a comment at the start of a word; e.g.: `${#args[@]}` is code.
+    line.char_indices()
+        .find(|(index, c)| {
+            *c == '#' && (*index == 0 || line[..*index].ends_with(char::is_whitespace))
+        })
+        .map(|(index, _)| &line[..index])
+        .unwrap_or(line)
+}
+
+fn same_dir(path: &Path, other: &Path) -> Result<bool> {
+    let canonicalize = |p: &Path| {
+        p.canonicalize()
+            .with_context(|| format!("Failed to canonicalize {p}", p = p.display()))
+    };
+    Ok(canonicalize(path)? == canonicalize(other)?)
+}
+
+pub(crate) fn get_pants_from_sources_process(env_pants_source: Option<String>) -> Result<Process> {
+    let build_root = BuildRoot::find(None)?;
+    let build_root_dir = build_root.to_path_buf();
+    let pants_config = PantsConfig::parse_if_present(build_root)?;
+    let user_config = UserConfig::load()?;
+    let pants_source = PantsSource::resolve(
+        env_pants_source,
+        &build_root_dir,
+        pants_config.as_ref(),
+        user_config.as_ref(),
+    );
+    pants_source
+        .validate(&build_root_dir)
+        .error_kind(ErrorKind::BadConfig)?;
+
+    let exe = pants_source.pants_script().into_os_string();
+
+    // By default, we turn off config verification since the consuming repo's config may well
+    // reference options that are not known to the Pants checkout.
+    let args = user_config
+        .as_ref()
+        .and_then(|uc| uc.config.pants_source_args.clone())
+        .or_else(|| {
+            pants_config
+                .as_ref()
+                .and_then(|pc| pc.config.scie_pants.pants_source_args.clone())
+        })
+        .unwrap_or_else(|| vec!["--no-verify-config".to_string()])
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+
+    // The Pants repo `./pants` script supports launching Pants under a debugpy server when
+    // PANTS_DEBUG is set; so we just need to make sure the checkout is new enough to have that
+    // support and normalize the value like we do for released Pants.
+    let pants_debug = matches!(env::var_os("PANTS_DEBUG"), Some(value) if !value.is_empty());
+    if pants_debug && !supports_pants_debug(&pants_source)? {
+        return Err(anyhow!(
+            "PANTS_DEBUG is set but the ./pants script of the Pants source directory {path} \
+            ({provenance}) does not support debugging. Please update your Pants checkout.",
+            path = pants_source.path.display(),
+            provenance = pants_source.provenance
+        ))
+        .error_kind(ErrorKind::BadConfig);
+    }
+
+    let version_file = pants_source.version_file();
+    let version = std::fs::read_to_string(&version_file).with_context(|| {
+        format!(
+            "Failed to read the Pants version from {version_file} ({provenance}).",
+            version_file = version_file.display(),
+            provenance = pants_source.provenance
+        )
+    })?;
+    let version = version.trim();
+    check_version(
+        &pants_source,
+        version,
+        pants_config.as_ref(),
+        user_config.as_ref(),
+    )
+    .error_kind(ErrorKind::BadConfig)?;
+
+    // The ENABLE_PANTSD env var is a custom env var defined by the legacy `./pants_from_sources`
+    // script. We maintain support here in perpetuity because it's cheap and we don't break folks'
+    // workflows.
+    let enable_pantsd = env::var_os("ENABLE_PANTSD")
+        .or_else(|| env::var_os("PANTS_PANTSD"))
+        .unwrap_or_else(|| "false".into());
+
+    let env = vec![
+        EnvOp::Set("PANTS_VERSION".into(), version.into()),
+        EnvOp::Set("PANTS_PANTSD".into(), enable_pantsd),
+        EnvOp::Set(
+            "PANTS_DEBUG".into(),
+            if pants_debug { "1" } else { "" }.into(),
+        ),
+        EnvOp::Set(
+            "PANTS_BUILDROOT_OVERRIDE".into(),
+            build_root_dir.as_os_str().to_os_string(),
+        ),
+        EnvOp::Set("no_proxy".into(), "*".into()),
+    ];
+
+    let env_allowlist = hermetic_env_allowlist(pants_config.as_ref(), user_config.as_ref());
+    let redact_patterns = redact_env_patterns(pants_config.as_ref(), user_config.as_ref());
+    Ok(Process {
+        exe,
+        args,
+        env,
+        ..Default::default()
+    }
+    .sourcing_pants_bootstrap(&build_root_dir)?
+    .with_env_allowlist(env_allowlist)
+    .redacting(redact_patterns))
+}
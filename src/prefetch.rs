@@ -0,0 +1,202 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::collections::BTreeSet;
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::build_root::BuildRoot;
+use crate::config::PantsConfig;
+use crate::error::{ErrorKind, WithErrorKind};
+use crate::version::{self, VersionAlias};
+use crate::{cache, releases};
+
+/// The CPython distributions packaged in the scie for each platform.
+const PYTHONS: &[&str] = &["python3.8", "python3.9"];
+
+pub(crate) struct PrefetchOptions {
+    pub(crate) pants_versions: Vec<String>,
+    pub(crate) build_roots: Vec<PathBuf>,
+    pub(crate) python: bool,
+}
+
+fn resolve(pants_version: String) -> Result<String> {
+    match VersionAlias::parse(&pants_version) {
+        Some(alias) => releases::resolve(&alias),
+        None => Ok(pants_version),
+    }
+}
+
+/// A Pants version to install along with the `[debugpy] version` configured alongside it, if any.
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd)]
+struct PantsInstall {
+    pants_version: String,
+    debugpy_version: Option<String>,
+}
+
+fn configured_pants_install(build_root: BuildRoot) -> Result<PantsInstall> {
+    let dir = build_root.to_path_buf();
+    let pants_config = PantsConfig::parse_if_present(build_root)?;
+    let pants_version = pants_config
+        .as_ref()
+        .and_then(|pants_config| pants_config.package_version())
+        .ok_or_else(|| {
+            anyhow!(
+                "No Pants version is configured for the build root {dir}.",
+                dir = dir.display()
+            )
+        })
+        .error_kind(ErrorKind::BadConfig)?;
+    Ok(PantsInstall {
+        pants_version: resolve(pants_version)?,
+        debugpy_version: pants_config.and_then(|pants_config| pants_config.debugpy_version()),
+    })
+}
+
+/// Determines the Pants versions to prefetch, in the order given, without duplicates.
+fn pants_installs(options: &PrefetchOptions) -> Result<Vec<PantsInstall>> {
+    let mut pants_installs = vec![];
+    for pants_version in &options.pants_versions {
+        version::validate_pants_version(pants_version)
+            .map_err(|problem| anyhow!("Invalid --pants-version: {problem}"))
+            .error_kind(ErrorKind::BadConfig)?;
+        pants_installs.push(PantsInstall {
+            pants_version: resolve(pants_version.clone())?,
+            debugpy_version: None,
+        });
+    }
+    for dir in &options.build_roots {
+        let build_root = BuildRoot::find(Some(dir.clone()))?;
+        pants_installs.push(configured_pants_install(build_root)?);
+    }
+    if pants_installs.is_empty() && !options.python {
+        pants_installs.push(configured_pants_install(BuildRoot::find(None)?)?);
+    }
+    let mut seen = BTreeSet::new();
+    pants_installs.retain(|pants_install| seen.insert(pants_install.clone()));
+    Ok(pants_installs)
+}
+
+/// Prepares to run the given scie boot command, which exists just to trigger the bindings and file
+/// extractions it depends on.
+fn boot_command(scie: &OsString, boot: &str) -> Command {
+    let mut command = Command::new(scie);
+    command
+        .env("SCIE_BOOT", boot)
+        .env_remove("SCIE_PANTS_COMMAND")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    command
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let output = command.output().with_context(|| {
+        format!(
+            "Failed to spawn {exe:?}",
+            exe = command.get_program().to_os_string()
+        )
+    })?;
+    if !output.status.success() {
+        bail!(
+            "{exe:?} exited with {status}:\n{stderr}",
+            exe = command.get_program().to_os_string(),
+            status = output.status,
+            stderr = String::from_utf8_lossy(&output.stderr).trim_end()
+        );
+    }
+    Ok(())
+}
+
+/// Installs the given Pants version; returning whether its venv was already installed.
+fn prefetch_pants(scie: &OsString, venv: &Path, pants_install: &PantsInstall) -> Result<bool> {
+    let installed = venv.is_dir();
+    let mut command = boot_command(scie, "prefetch-pants");
+    // N.B.: This mirrors the env the launcher runs the configure and install bindings with; so a
+    // later Pants launch finds their results cached. Since the install binding is keyed by the
+    // debugpy version too, we run it even when the venv exists; scie-jump skips it if cached.
+    command
+        .env("PANTS_VERSION", &pants_install.pants_version)
+        .env("PANTS_DEBUG", "");
+    match pants_install.debugpy_version {
+        Some(ref debugpy_version) => command.env("PANTS_DEBUGPY_VERSION", debugpy_version),
+        None => command.env_remove("PANTS_DEBUGPY_VERSION"),
+    };
+    for name in [
+        "PANTS_SHA",
+        "PANTS_TOML",
+        "PANTS_VERSION_PROMPT_SALT",
+        "PEX_ROOT",
+        "VIRTUAL_ENV",
+    ] {
+        command.env_remove(name);
+    }
+    run(&mut command)?;
+    Ok(installed)
+}
+
+/// Fetches the given CPython distribution unless already cached; returning whether it was.
+fn prefetch_python(scie: &OsString, path: &Path, python: &str) -> Result<bool> {
+    if path.exists() {
+        return Ok(true);
+    }
+    run(boot_command(scie, "prefetch-python").env("SCIE_PANTS_PREFETCH_PYTHON", python))?;
+    Ok(false)
+}
+
+fn report(subject: &str, result: Result<bool>, fetched: &str) -> bool {
+    match result {
+        Ok(true) => {
+            println!("{subject}: already cached");
+            true
+        }
+        Ok(false) => {
+            println!("{subject}: {fetched}");
+            true
+        }
+        Err(err) => {
+            println!("{subject}: failed: {err:#}");
+            false
+        }
+    }
+}
+
+/// Installs Pants versions and fetches CPython distributions into the nce cache without running
+/// Pants; returning the exit code.
+pub(crate) fn prefetch(options: PrefetchOptions) -> Result<i32> {
+    let bindings_dir = cache::bindings_dir()?;
+    let scie = env::var_os("SCIE")
+        .context("Failed to retrieve SCIE location from the environment.")
+        .error_kind(ErrorKind::MissingScie)?;
+    let pants_installs = pants_installs(&options)?;
+
+    let mut success = true;
+    if options.python {
+        for python in PYTHONS {
+            let (name, path) = cache::python_distribution(&bindings_dir, python)?;
+            success &= report(
+                &format!("CPython {name}"),
+                prefetch_python(&scie, &path, python),
+                "fetched",
+            );
+        }
+    }
+    for pants_install in &pants_installs {
+        let pants_version = &pants_install.pants_version;
+        let venv = cache::venv_dir(&bindings_dir, pants_version, None);
+        let subject = match pants_install.debugpy_version {
+            Some(ref debugpy_version) => format!("Pants {pants_version} [{debugpy_version}]"),
+            None => format!("Pants {pants_version}"),
+        };
+        success &= report(
+            &subject,
+            prefetch_pants(&scie, &venv, pants_install),
+            &format!("installed at {venv}", venv = venv.display()),
+        );
+    }
+    Ok(if success { 0 } else { 1 })
+}
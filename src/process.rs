@@ -0,0 +1,319 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use log::debug;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+use crate::error::ErrorKind;
+
+/// An operation on the environment inherited by a `Process`. Operations are applied in order.
+#[derive(Clone, Debug)]
+pub(crate) enum EnvOp {
+    Set(OsString, OsString),
+    Unset(OsString),
+}
+
+impl EnvOp {
+    fn name(&self) -> &OsStr {
+        match self {
+            EnvOp::Set(name, _) | EnvOp::Unset(name) => name,
+        }
+    }
+
+    fn apply(&self, env: &mut Vec<(OsString, OsString)>) {
+        let existing = env.iter().position(|(name, _)| name == self.name());
+        match self {
+            EnvOp::Set(name, value) => match existing {
+                Some(index) => env[index].1 = value.clone(),
+                None => env.push((name.clone(), value.clone())),
+            },
+            EnvOp::Unset(_) => {
+                if let Some(index) = existing {
+                    env.remove(index);
+                }
+            }
+        }
+    }
+}
+
+impl Serialize for EnvOp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (op, value) = match self {
+            EnvOp::Set(_, value) => ("set", Some(value)),
+            EnvOp::Unset(_) => ("unset", None),
+        };
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("op", op)?;
+        map.serialize_entry("name", &self.name().to_string_lossy())?;
+        if let Some(value) = value {
+            map.serialize_entry("value", &value.to_string_lossy())?;
+        }
+        map.end()
+    }
+}
+
+/// The env var name patterns whose values are always masked when a `Process` is rendered.
+const REDACTED_ENV_PATTERNS: &[&str] = &[
+    "*TOKEN*",
+    "*SECRET*",
+    "*PASSWORD*",
+    "*PASSWD*",
+    "*CREDENTIAL*",
+    "*API_KEY*",
+    "*PRIVATE_KEY*",
+];
+
+/// Masks the values of env vars whose names match any of a set of case-insensitive patterns, where
+/// `*` matches any run of characters.
+#[derive(Clone, Debug)]
+pub(crate) struct Redactor {
+    patterns: Vec<String>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self {
+            patterns: REDACTED_ENV_PATTERNS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Redactor {
+    fn matches(&self, name: &OsStr) -> bool {
+        let name = name.to_string_lossy().to_ascii_uppercase();
+        self.patterns
+            .iter()
+            .any(|pattern| glob_match(&pattern.to_ascii_uppercase(), &name))
+    }
+
+    fn redact(&self, env_op: &EnvOp) -> EnvOp {
+        let redacted = || OsString::from("<redacted>");
+        match env_op {
+            EnvOp::Set(name, _) if self.matches(name) => EnvOp::Set(name.clone(), redacted()),
+            env_op => env_op.clone(),
+        }
+    }
+}
+
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // N.B.: `split` always yields at least one item.
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        // There were no `*`s; so the pattern must match exactly.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[derive(Default)]
+pub(crate) struct Process {
+    pub(crate) exe: OsString,
+    pub(crate) args: Vec<OsString>,
+    pub(crate) env: Vec<EnvOp>,
+    pub(crate) redactor: Redactor,
+}
+
+impl Debug for Process {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Process")
+            .field("exe", &self.exe)
+            .field("args", &self.args)
+            .field("env", &self.redacted_env())
+            .finish()
+    }
+}
+
+impl Serialize for Process {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("exe", &self.exe.to_string_lossy())?;
+        map.serialize_entry(
+            "args",
+            &self
+                .args
+                .iter()
+                .map(|arg| arg.to_string_lossy())
+                .collect::<Vec<_>>(),
+        )?;
+        map.serialize_entry("env", &self.redacted_env())?;
+        map.end()
+    }
+}
+
+impl Process {
+    /// Returns the full environment the process will be executed with: the current environment
+    /// with this process's env operations applied.
+    fn environment(&self) -> Vec<(OsString, OsString)> {
+        let mut environment = env::vars_os().collect::<Vec<_>>();
+        for env_op in &self.env {
+            env_op.apply(&mut environment);
+        }
+        environment
+    }
+
+    /// The env operations of this process with the values of any sensitive env vars masked, for
+    /// use in logs and other output users may share.
+    fn redacted_env(&self) -> Vec<EnvOp> {
+        self.env
+            .iter()
+            .map(|env_op| self.redactor.redact(env_op))
+            .collect()
+    }
+
+    /// Additionally masks the values of env vars matching the given patterns when this process is
+    /// rendered.
+    pub(crate) fn redacting(mut self, patterns: Vec<String>) -> Process {
+        self.redactor.patterns.extend(patterns);
+        self
+    }
+
+    /// If an allowlist is given, arranges for all inherited env vars it does not match to be unset
+    /// before this process's own env operations are applied. Allowlist entries ending in `*` match
+    /// by prefix.
+    pub(crate) fn with_env_allowlist(mut self, allowlist: Option<Vec<String>>) -> Process {
+        let Some(allowlist) = allowlist else {
+            return self;
+        };
+        let allowed = |name: &str| {
+            allowlist.iter().any(|entry| match entry.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => name == entry,
+            })
+        };
+        let dropped = env::vars_os()
+            .map(|(name, _)| name)
+            .filter(|name| !allowed(&name.to_string_lossy()))
+            .collect::<Vec<_>>();
+        if !dropped.is_empty() {
+            debug!(
+                "Hermetic env mode is dropping the following env vars: {dropped}",
+                dropped = dropped
+                    .iter()
+                    .map(|name| name.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        let mut env = dropped.into_iter().map(EnvOp::Unset).collect::<Vec<_>>();
+        env.extend(self.env);
+        self.env = env;
+        self
+    }
+
+    #[cfg(windows)]
+    pub(crate) fn exec(self) -> Result<i32> {
+        use std::process::Command;
+
+        let exit_status = Command::new(&self.exe)
+            .args(&self.args)
+            .args(env::args().skip(1))
+            .env_clear()
+            .envs(self.environment())
+            .spawn()?
+            .wait()
+            .with_context(|| format!("Failed to execute process: {self:#?}"))?;
+        Ok(exit_status
+            .code()
+            .unwrap_or_else(|| if exit_status.success() { 0 } else { 1 }))
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn exec(self) -> Result<i32> {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStringExt;
+
+        use nix::unistd::execve;
+
+        let c_env = self
+            .environment()
+            .into_iter()
+            .map(|(name, value)| {
+                let mut entry = name.into_vec();
+                entry.push(b'=');
+                entry.extend(value.into_vec());
+                CString::new(entry).context("Failed to convert env var to a C string.")
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let c_exe = CString::new(self.exe.into_vec())
+            .context("Failed to convert executable to a C string.")?;
+
+        let mut c_args = vec![c_exe.clone()];
+        c_args.extend(
+            self.args
+                .into_iter()
+                .chain(env::args().skip(1).map(OsString::from))
+                .map(|arg| {
+                    CString::new(arg.into_vec())
+                        .context("Failed to convert argument to a C string.")
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+
+        execve(&c_exe, &c_args, &c_env)
+            .map(|_| 0)
+            .context("Failed to exec process.")
+    }
+
+    #[cfg(unix)]
+    fn quote<T: Into<OsString> + Debug>(value: T) -> Result<String> {
+        String::from_utf8(shell_quote::bash::escape(value))
+            .context("Shell-quoted value could not be interpreted as UTF-8.")
+    }
+
+    #[cfg(windows)]
+    fn quote<T: Into<OsString> + Debug>(_value: T) -> Result<String> {
+        // The shell_quote crate assumes unix and fails to compile on Windows.
+        todo!("TODO(John Sirois): Figure out Git bash? shell quoting for Windows WTF-16 strings.")
+    }
+
+    /// Wraps this process in a bash shell that first sources the `.pants.bootstrap` script in the
+    /// given build root, if there is one.
+    pub(crate) fn sourcing_pants_bootstrap(self, build_root: &Path) -> Result<Process> {
+        let pants_bootstrap = build_root.join(".pants.bootstrap");
+        if !pants_bootstrap.is_file() {
+            return Ok(self);
+        }
+        let mut args: Vec<OsString> = vec![
+            "bash".into(),
+            "-c".into(),
+            format!(
+                r#"set -eou pipefail; trap '[ $? -eq 0 ] || exit {exit_code}' EXIT; source {bootstrap}; trap - EXIT; exec {exe} "$@""#,
+                exit_code = ErrorKind::BootstrapScriptFailed.exit_code(),
+                bootstrap = Self::quote(pants_bootstrap)?,
+                exe = Self::quote(self.exe.clone())?
+            )
+            .into(),
+            // N.B.: This is `$0` for the bash script above.
+            self.exe,
+        ];
+        args.extend(self.args);
+        Ok(Process {
+            exe: "/usr/bin/env".into(),
+            args,
+            env: self.env,
+            redactor: self.redactor,
+        })
+    }
+}
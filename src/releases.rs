@@ -0,0 +1,245 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{now, store_json};
+use crate::env_flag;
+use crate::error::{ErrorKind, WithErrorKind};
+use crate::version::{Version, VersionAlias};
+
+/// The Pants release tags known when this scie-pants was built; used to resolve version aliases
+/// when PyPI cannot be reached and no resolution is cached.
+const EMBEDDED_RELEASE_TAGS: &str = include_str!("../tools/src/scie_pants/pants_release_tags.json");
+
+const PYPI_PANTS_URL: &str = "https://pypi.org/pypi/pantsbuild.pants/json";
+
+/// How long a cached version alias resolution is used before PyPI is consulted again.
+const DEFAULT_ALIAS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Clone, Deserialize, Serialize)]
+struct CachedResolution {
+    version: String,
+    /// Seconds since the UNIX epoch.
+    resolved_at: u64,
+}
+
+#[derive(Deserialize)]
+struct PypiRelease {
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Deserialize)]
+struct PypiProject {
+    releases: BTreeMap<String, Vec<PypiRelease>>,
+}
+
+fn alias_ttl() -> Result<Duration> {
+    match env::var("PANTS_BOOTSTRAP_VERSION_ALIAS_TTL") {
+        Ok(seconds) => seconds
+            .trim()
+            .parse()
+            .map(Duration::from_secs)
+            .map_err(|_| {
+                anyhow!(
+                    "Invalid PANTS_BOOTSTRAP_VERSION_ALIAS_TTL: {seconds} is not a number of \
+                    seconds"
+                )
+            })
+            .error_kind(ErrorKind::BadConfig),
+        Err(_) => Ok(DEFAULT_ALIAS_TTL),
+    }
+}
+
+fn embedded_releases() -> Vec<Version> {
+    let tags: BTreeMap<String, String> = serde_json::from_str(EMBEDDED_RELEASE_TAGS)
+        .expect("The embedded Pants release tags are valid JSON.");
+    tags.keys()
+        .filter_map(|tag| tag.strip_prefix("release_"))
+        .filter_map(Version::parse)
+        .collect()
+}
+
+/// Parses Pants release data in the PyPI JSON API format, omitting releases with no distributions
+/// or only yanked ones.
+fn parse_pypi_releases(data: &[u8]) -> serde_json::Result<Vec<Version>> {
+    let project: PypiProject = serde_json::from_slice(data)?;
+    Ok(project
+        .releases
+        .into_iter()
+        .filter(|(_, dists)| dists.iter().any(|dist| !dist.yanked))
+        .filter_map(|(version, _)| Version::parse(&version))
+        .collect())
+}
+
+/// Fetches the Pants releases from PyPI.
+fn pypi_releases(ptex: &Path) -> Result<Vec<Version>> {
+    let output = Command::new(ptex)
+        .arg(PYPI_PANTS_URL)
+        .output()
+        .with_context(|| {
+            format!(
+                "Failed to spawn {ptex} to fetch {PYPI_PANTS_URL}",
+                ptex = ptex.display()
+            )
+        })?;
+    if !output.status.success() {
+        bail!(
+            "Failed to fetch {PYPI_PANTS_URL}: {stderr}",
+            stderr = String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_pypi_releases(&output.stdout)
+        .with_context(|| format!("Failed to parse the Pants release data from {PYPI_PANTS_URL}"))
+}
+
+fn fetch_releases() -> Result<Vec<Version>> {
+    if env_flag("PANTS_BOOTSTRAP_OFFLINE").unwrap_or_default() {
+        bail!("PANTS_BOOTSTRAP_OFFLINE is set.");
+    }
+    match env::var_os("SCIE_PANTS_PTEX") {
+        Some(ptex) => pypi_releases(Path::new(&ptex)),
+        None => Err(anyhow!("The SCIE_PANTS_PTEX env var is not set.")),
+    }
+}
+
+/// Returns all the Pants releases.
+///
+/// The releases are read from the given file in the PyPI JSON API format if any; otherwise they
+/// are fetched from PyPI.
+pub(crate) fn index(releases_file: Option<&Path>) -> Result<Vec<Version>> {
+    if let Some(releases_file) = releases_file {
+        let data = std::fs::read(releases_file)
+            .with_context(|| format!("Failed to read {path}", path = releases_file.display()))
+            .error_kind(ErrorKind::BadConfig)?;
+        return parse_pypi_releases(&data)
+            .with_context(|| {
+                format!(
+                    "Failed to parse {path} as Pants release data in the PyPI JSON API format",
+                    path = releases_file.display()
+                )
+            })
+            .error_kind(ErrorKind::BadConfig);
+    }
+    fetch_releases().context(
+        "Failed to determine the current Pants releases. If you are offline, pass \
+        --releases-file with a saved copy of https://pypi.org/pypi/pantsbuild.pants/json.",
+    )
+}
+
+struct AliasCache {
+    path: PathBuf,
+    resolutions: BTreeMap<String, CachedResolution>,
+}
+
+impl AliasCache {
+    fn load(bindings_dir: &Path) -> Self {
+        let path = bindings_dir.join("pants-version-aliases.json");
+        let resolutions = std::fs::read(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_slice(&contents).ok())
+            .unwrap_or_default();
+        Self { path, resolutions }
+    }
+
+    fn store(&mut self, alias: &VersionAlias, version: &Version) -> Result<()> {
+        self.resolutions.insert(
+            alias.to_string(),
+            CachedResolution {
+                version: version.to_string(),
+                resolved_at: now()?,
+            },
+        );
+        store_json(&self.path, &self.resolutions)
+            .context("Failed to update the version alias cache.")
+    }
+}
+
+/// Returns the cached resolution of a Pants version alias, however stale, without consulting PyPI.
+pub(crate) fn cached_resolution(alias: &VersionAlias) -> Option<String> {
+    let bindings_dir = env::var_os("SCIE_PANTS_BINDINGS")?;
+    AliasCache::load(Path::new(&bindings_dir))
+        .resolutions
+        .remove(&alias.to_string())
+        .map(|cached| cached.version)
+}
+
+/// Resolves a Pants version alias to a concrete Pants release version.
+///
+/// PyPI is consulted at most once per TTL period; failing that, a stale cached resolution or the
+/// releases known when this scie-pants was built are used, with a warning, so that aliases
+/// still work offline.
+pub(crate) fn resolve(alias: &VersionAlias) -> Result<String> {
+    let ttl = alias_ttl()?;
+    let mut cache = env::var_os("SCIE_PANTS_BINDINGS").map(|dir| AliasCache::load(Path::new(&dir)));
+    let cached = cache
+        .as_ref()
+        .and_then(|cache| cache.resolutions.get(&alias.to_string()))
+        .cloned();
+    if let Some(ref cached) = cached {
+        if now()?.saturating_sub(cached.resolved_at) < ttl.as_secs() {
+            debug!(
+                "Using cached resolution of Pants version {alias}: {version}",
+                version = cached.version
+            );
+            return Ok(cached.version.clone());
+        }
+    }
+
+    let err = match fetch_releases() {
+        Ok(releases) => {
+            let version = alias
+                .select(&releases)
+                .ok_or_else(|| {
+                    anyhow!("There is no Pants release matching the Pants version {alias}.")
+                })
+                .error_kind(ErrorKind::BadConfig)?;
+            if let Some(ref mut cache) = cache {
+                if let Err(err) = cache.store(alias, version) {
+                    warn!("Failed to cache the resolution of Pants version {alias}: {err:#}");
+                }
+            }
+            return Ok(version.to_string());
+        }
+        Err(err) => err,
+    };
+    if let Some(cached) = cached {
+        eprintln!(
+            "WARNING: Using a stale resolution of Pants version {alias} to {version} since the \
+            current Pants releases could not be determined: {err:#}",
+            version = cached.version
+        );
+        return Ok(cached.version);
+    }
+
+    // N.B.: The embedded releases are a snapshot; so the newest pre-release of a minor line they
+    // know of has likely been superseded by a stable release since.
+    let embedded_releases = embedded_releases()
+        .into_iter()
+        .filter(|version| !matches!(alias, VersionAlias::MinorLine(..)) || !version.is_prerelease())
+        .collect::<Vec<_>>();
+    let version = alias
+        .select(&embedded_releases)
+        .ok_or_else(|| {
+            anyhow!(
+                "Cannot resolve the Pants version {alias} since the current Pants releases could \
+                not be determined and scie-pants knows of no stable release matching it: {err:#}"
+            )
+        })
+        .error_kind(ErrorKind::Offline)?;
+    eprintln!(
+        "WARNING: Resolved Pants version {alias} to {version} using the Pants releases known when \
+        this scie-pants was built since the current Pants releases could not be determined: \
+        {err:#}"
+    );
+    Ok(version.to_string())
+}
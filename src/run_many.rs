@@ -0,0 +1,220 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::collections::VecDeque;
+use std::env;
+use std::ffi::OsString;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::build_root::BuildRoot;
+use crate::config::PantsConfig;
+use crate::error::{ErrorKind, WithErrorKind};
+use crate::process::glob_match;
+
+pub(crate) struct RunManyOptions {
+    pub(crate) dirs: Vec<PathBuf>,
+    pub(crate) globs: Vec<String>,
+    pub(crate) jobs: usize,
+    pub(crate) pants_args: Vec<OsString>,
+}
+
+/// The outcome of running Pants in one build root.
+struct Outcome {
+    dir: PathBuf,
+    pants_version: String,
+    status: String,
+    success: bool,
+}
+
+/// Expands a glob pattern whose path components may contain `*` wildcards into the matching
+/// directories.
+fn expand_glob(pattern: &str) -> Vec<PathBuf> {
+    let mut matches = vec![PathBuf::new()];
+    for component in Path::new(pattern).components() {
+        let name = match component {
+            Component::Normal(name) => name.to_string_lossy(),
+            other => {
+                for path in &mut matches {
+                    path.push(other);
+                }
+                continue;
+            }
+        };
+        if !name.contains('*') {
+            for path in &mut matches {
+                path.push(name.as_ref());
+            }
+            continue;
+        }
+        matches = matches
+            .iter()
+            .flat_map(|parent| {
+                let dir = if parent.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    parent.as_path()
+                };
+                std::fs::read_dir(dir)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|entry| {
+                        let file_name = entry.file_name().to_string_lossy().into_owned();
+                        // N.B.: As in shells, wildcards do not match hidden files.
+                        (!file_name.starts_with('.') || name.starts_with('.'))
+                            && glob_match(&name, &file_name)
+                    })
+                    .map(|entry| parent.join(entry.file_name()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+    let mut dirs = matches
+        .into_iter()
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    dirs
+}
+
+fn run_pants(scie: &OsString, dir: &Path, pants_args: &[OsString], capture: bool) -> Outcome {
+    let failure = |pants_version: String, err: anyhow::Error| Outcome {
+        dir: dir.to_path_buf(),
+        pants_version,
+        status: format!("error: {err}"),
+        success: false,
+    };
+    let build_root = match BuildRoot::find(Some(dir.to_path_buf())) {
+        Ok(build_root) => build_root,
+        Err(err) => return failure("-".to_string(), err),
+    };
+    let pants_version = match PantsConfig::parse_if_present(build_root) {
+        Ok(pants_config) => pants_config
+            .and_then(|pants_config| pants_config.package_version())
+            .unwrap_or_else(|| "-".to_string()),
+        Err(err) => return failure("-".to_string(), err),
+    };
+
+    let mut command = Command::new(scie);
+    command
+        .args(pants_args)
+        .current_dir(dir)
+        // N.B.: These select this command; so we clear them to launch Pants instead.
+        .env_remove("SCIE_BOOT")
+        .env_remove("SCIE_PANTS_COMMAND");
+    let result = if capture {
+        command.stdin(Stdio::null()).output().map(|output| {
+            // N.B.: We hold both locks so that the output of each directory is not interleaved
+            // with that of others finishing at the same time.
+            let mut stdout = std::io::stdout().lock();
+            let mut stderr = std::io::stderr().lock();
+            let _ = writeln!(stdout, "==> {dir} <==", dir = dir.display());
+            let _ = stdout.write_all(&output.stdout);
+            let _ = stdout.flush();
+            let _ = stderr.write_all(&output.stderr);
+            output.status
+        })
+    } else {
+        println!("==> {dir} <==", dir = dir.display());
+        command.status()
+    };
+    match result {
+        Ok(status) => Outcome {
+            dir: dir.to_path_buf(),
+            pants_version,
+            status: status
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "killed".to_string()),
+            success: status.success(),
+        },
+        Err(err) => failure(
+            pants_version,
+            anyhow!(err).context(format!("Failed to spawn {scie:?}")),
+        ),
+    }
+}
+
+fn print_summary(outcomes: &[Outcome]) {
+    let dirs = outcomes
+        .iter()
+        .map(|outcome| outcome.dir.display().to_string())
+        .collect::<Vec<_>>();
+    let dir_width = dirs
+        .iter()
+        .map(String::len)
+        .chain(["Build root".len()])
+        .max();
+    let version_width = outcomes
+        .iter()
+        .map(|outcome| outcome.pants_version.len())
+        .chain(["Pants version".len()])
+        .max();
+    let (dir_width, version_width) = (dir_width.unwrap_or(0), version_width.unwrap_or(0));
+    println!();
+    println!(
+        "{:dir_width$}  {:version_width$}  Exit code",
+        "Build root", "Pants version"
+    );
+    for (dir, outcome) in dirs.iter().zip(outcomes) {
+        println!(
+            "{dir:dir_width$}  {version:version_width$}  {status}",
+            version = outcome.pants_version,
+            status = outcome.status
+        );
+    }
+}
+
+/// Runs Pants with the given args in each of the given build roots; returning the exit code.
+pub(crate) fn run_many(options: RunManyOptions) -> Result<i32> {
+    let mut dirs = options.dirs;
+    for pattern in &options.globs {
+        dirs.extend(expand_glob(pattern));
+    }
+    if dirs.is_empty() {
+        return Err(anyhow!(
+            "No build root directories were given or matched by the given globs."
+        ))
+        .error_kind(ErrorKind::NoBuildRoot);
+    }
+    let scie = env::var_os("SCIE")
+        .context("Failed to retrieve SCIE location from the environment.")
+        .error_kind(ErrorKind::MissingScie)?;
+
+    let jobs = options.jobs.max(1).min(dirs.len());
+    let capture = jobs > 1;
+    let queue = Mutex::new(dirs.iter().enumerate().collect::<VecDeque<_>>());
+    let outcomes = Mutex::new(Vec::with_capacity(dirs.len()));
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let Some((index, dir)) = queue.lock().expect("Not poisoned.").pop_front() else {
+                    break;
+                };
+                let outcome = run_pants(&scie, dir, &options.pants_args, capture);
+                outcomes
+                    .lock()
+                    .expect("Not poisoned.")
+                    .push((index, outcome));
+            });
+        }
+    });
+    let mut outcomes = outcomes.into_inner().expect("Not poisoned.");
+    outcomes.sort_by_key(|(index, _)| *index);
+    let outcomes = outcomes
+        .into_iter()
+        .map(|(_, outcome)| outcome)
+        .collect::<Vec<_>>();
+
+    print_summary(&outcomes);
+    Ok(if outcomes.iter().all(|outcome| outcome.success) {
+        0
+    } else {
+        1
+    })
+}
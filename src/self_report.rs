@@ -0,0 +1,54 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::lift::{self, File};
+
+#[derive(Serialize)]
+struct Report {
+    version: &'static str,
+    git_commit: &'static str,
+    build_date: &'static str,
+    target: &'static str,
+    scie_jump_tag: &'static str,
+    ptex_tag: &'static str,
+    tools_pex_hash: Option<String>,
+    python_distributions: Vec<File>,
+    /// Problems encountered inspecting the scie; the build information is still useful.
+    errors: Vec<String>,
+}
+
+/// Prints a JSON description of this scie-pants binary and the scie it was packaged in.
+pub(crate) fn print() -> Result<()> {
+    let mut report = Report {
+        version: env!("CARGO_PKG_VERSION"),
+        git_commit: env!("SCIE_PANTS_GIT_COMMIT"),
+        build_date: env!("SCIE_PANTS_BUILD_DATE"),
+        target: env!("SCIE_PANTS_TARGET"),
+        scie_jump_tag: env!("SCIE_PANTS_SCIE_JUMP_TAG"),
+        ptex_tag: env!("SCIE_PANTS_PTEX_TAG"),
+        tools_pex_hash: None,
+        python_distributions: vec![],
+        errors: vec![],
+    };
+    match lift::files() {
+        Ok(files) => {
+            for file in files {
+                if file.name == "tools.pex" {
+                    report.tools_pex_hash = file.hash;
+                } else if file.name.starts_with("cpython-") {
+                    report.python_distributions.push(file);
+                }
+            }
+        }
+        Err(err) => report.errors.push(format!("{err:#}")),
+    }
+    println!(
+        "{report}",
+        report = serde_json::to_string_pretty(&report)
+            .context("Failed to serialize the scie-pants self-report as JSON.")?
+    );
+    Ok(())
+}
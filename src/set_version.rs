@@ -0,0 +1,127 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use toml_edit::{Document, Item, Value};
+
+use crate::build_root::BuildRoot;
+use crate::config::PantsConfig;
+use crate::error::{ErrorKind, WithErrorKind};
+use crate::version;
+
+/// A change of `[GLOBAL] pants_version` in a Pants config file.
+pub(crate) struct PantsVersionEdit {
+    pub(crate) path: PathBuf,
+    pub(crate) previous_version: Option<String>,
+    pub(crate) contents: String,
+    pub(crate) edited_contents: String,
+}
+
+impl PantsVersionEdit {
+    /// Computes the edit setting `[GLOBAL] pants_version` in the given Pants config file, preserving
+    /// its comments, formatting and ordering.
+    pub(crate) fn compute(path: &Path, pants_version: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {path}", path = path.display()))
+            .error_kind(ErrorKind::BadConfig)?;
+        let mut document = contents
+            .parse::<Document>()
+            .with_context(|| format!("Failed to parse {path}", path = path.display()))
+            .error_kind(ErrorKind::BadConfig)?;
+        let global = document
+            .entry("GLOBAL")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Expected GLOBAL to be a table in {path}",
+                    path = path.display()
+                )
+            })
+            .error_kind(ErrorKind::BadConfig)?;
+        let previous_version = match global.get_mut("pants_version") {
+            Some(Item::Value(value)) if value.as_str() == Some(pants_version) => {
+                Some(pants_version.to_string())
+            }
+            Some(Item::Value(value)) => {
+                let previous_version = value.as_str().map(str::to_string);
+                // N.B.: Keep any comments and whitespace surrounding the old value.
+                let decor = value.decor().clone();
+                *value = Value::from(pants_version);
+                *value.decor_mut() = decor;
+                previous_version
+            }
+            _ => {
+                global.insert("pants_version", toml_edit::value(pants_version));
+                None
+            }
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            previous_version,
+            contents,
+            edited_contents: document.to_string(),
+        })
+    }
+
+    pub(crate) fn is_change(&self) -> bool {
+        self.contents != self.edited_contents
+    }
+
+    pub(crate) fn apply(&self) -> Result<()> {
+        std::fs::write(&self.path, &self.edited_contents)
+            .with_context(|| format!("Failed to write {path}", path = self.path.display()))
+    }
+}
+
+/// Finds the Pants config file to edit; the one the launcher reads `pants_version` from by default.
+pub(crate) fn pants_config_path(config: Option<PathBuf>) -> Result<PathBuf> {
+    match config {
+        Some(config) => Ok(config),
+        None => {
+            let build_root = BuildRoot::find(None)?;
+            Ok(PantsConfig::locate(&build_root).0)
+        }
+    }
+}
+
+/// Sets `[GLOBAL] pants_version` in the Pants config file; returning the exit code.
+///
+/// In check mode, nothing is written and the exit code is non-zero if the file would change.
+pub(crate) fn set_version(
+    pants_version: &str,
+    config: Option<PathBuf>,
+    check: bool,
+) -> Result<i32> {
+    version::validate_pants_version(pants_version)
+        .map_err(|problem| anyhow!("Invalid Pants version: {problem}"))
+        .error_kind(ErrorKind::BadConfig)?;
+    let path = pants_config_path(config)?;
+    let edit = PantsVersionEdit::compute(&path, pants_version)?;
+    if !edit.is_change() {
+        println!(
+            "The pants_version is already {pants_version} in {path}",
+            path = path.display()
+        );
+        return Ok(0);
+    }
+    let change = match edit.previous_version {
+        Some(ref previous_version) => format!("from {previous_version} to {pants_version}"),
+        None => format!("to {pants_version}"),
+    };
+    if check {
+        eprintln!(
+            "The pants_version in {path} would change {change}.",
+            path = path.display()
+        );
+        return Ok(1);
+    }
+    edit.apply()?;
+    println!(
+        "Changed the pants_version in {path} {change}.",
+        path = path.display()
+    );
+    Ok(0)
+}
@@ -0,0 +1,242 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use toml_edit::Document;
+
+use crate::error::{ErrorKind, WithErrorKind};
+use crate::releases;
+use crate::set_version::{pants_config_path, PantsVersionEdit};
+use crate::version::{self, Version, VersionAlias};
+
+const UPGRADE_TIPS_URL: &str = "https://www.pantsbuild.org/docs/upgrade-tips";
+
+/// The release to upgrade Pants to.
+#[derive(Clone, Debug)]
+pub(crate) enum UpgradeTarget {
+    /// The newest stable release in the current release line.
+    LatestPatch,
+    /// The newest stable release in the release line after the current one.
+    NextMinor,
+    /// A specific version or version alias.
+    Version(String),
+}
+
+impl FromStr for UpgradeTarget {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "latest-patch" => Ok(UpgradeTarget::LatestPatch),
+            "next-minor" => Ok(UpgradeTarget::NextMinor),
+            _ => {
+                version::validate_pants_version(value)?;
+                Ok(UpgradeTarget::Version(value.to_string()))
+            }
+        }
+    }
+}
+
+pub(crate) struct UpgradeOptions {
+    pub(crate) to: UpgradeTarget,
+    pub(crate) config: Option<PathBuf>,
+    pub(crate) releases_file: Option<PathBuf>,
+    pub(crate) dry_run: bool,
+}
+
+fn current_version(path: &Path, releases: &[Version]) -> Result<Version> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {path}", path = path.display()))
+        .error_kind(ErrorKind::BadConfig)?;
+    let document = contents
+        .parse::<Document>()
+        .with_context(|| format!("Failed to parse {path}", path = path.display()))
+        .error_kind(ErrorKind::BadConfig)?;
+    let pants_version = document
+        .get("GLOBAL")
+        .and_then(|global| global.get("pants_version"))
+        .and_then(|pants_version| pants_version.as_str())
+        .ok_or_else(|| {
+            anyhow!(
+                "There is no `[GLOBAL] pants_version` in {path} to upgrade from. Use \
+                `SCIE_BOOT=set-version scie-pants <version>` to set one.",
+                path = path.display()
+            )
+        })
+        .error_kind(ErrorKind::BadConfig)?;
+    match VersionAlias::parse(pants_version) {
+        Some(alias) => alias.select(releases).cloned(),
+        None => Version::parse(pants_version),
+    }
+    .ok_or_else(|| {
+        anyhow!(
+            "Failed to determine the Pants version {pants_version} configured in {path}.",
+            path = path.display()
+        )
+    })
+    .error_kind(ErrorKind::BadConfig)
+}
+
+fn newest_stable_in_line(releases: &[Version], line: (u64, u64)) -> Option<&Version> {
+    releases
+        .iter()
+        .filter(|release| release.line() == line && !release.is_prerelease())
+        .max()
+}
+
+fn target_version(
+    target: &UpgradeTarget,
+    current: &Version,
+    releases: &[Version],
+) -> Result<Version> {
+    let (major, minor) = current.line();
+    match target {
+        UpgradeTarget::LatestPatch => newest_stable_in_line(releases, (major, minor))
+            .cloned()
+            .ok_or_else(|| anyhow!("There is no stable Pants {major}.{minor}.x release.")),
+        UpgradeTarget::NextMinor => newest_stable_in_line(releases, (major, minor + 1))
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "There is no stable Pants {major}.{next_minor}.x release yet.",
+                    next_minor = minor + 1
+                )
+            }),
+        UpgradeTarget::Version(version) => match VersionAlias::parse(version) {
+            Some(alias) => alias
+                .select(releases)
+                .cloned()
+                .ok_or_else(|| anyhow!("There is no Pants release matching {alias}.")),
+            None => Version::parse(version)
+                .ok_or_else(|| anyhow!("Failed to parse the Pants version {version}.")),
+        },
+    }
+    .error_kind(ErrorKind::BadConfig)
+}
+
+/// Renders a unified diff of the given texts with a single hunk spanning the changed lines.
+fn unified_diff(path: &Path, before: &str, after: &str) -> String {
+    let before = before.lines().collect::<Vec<_>>();
+    let after = after.lines().collect::<Vec<_>>();
+    let prefix = before
+        .iter()
+        .zip(after.iter())
+        .take_while(|(b, a)| b == a)
+        .count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(b, a)| b == a)
+        .count();
+    let context = 3;
+    let start = prefix.saturating_sub(context);
+    let before_end = (before.len() - suffix + context).min(before.len());
+    let after_end = (after.len() - suffix + context).min(after.len());
+
+    let mut diff = format!(
+        "--- {path}\n+++ {path}\n@@ -{start},{before_count} +{start},{after_count} @@\n",
+        path = path.display(),
+        start = start + 1,
+        before_count = before_end - start,
+        after_count = after_end - start,
+    );
+    for line in &before[start..prefix] {
+        diff.push_str(&format!(" {line}\n"));
+    }
+    for line in &before[prefix..before.len() - suffix] {
+        diff.push_str(&format!("-{line}\n"));
+    }
+    for line in &after[prefix..after.len() - suffix] {
+        diff.push_str(&format!("+{line}\n"));
+    }
+    for line in &after[after.len() - suffix..after_end] {
+        diff.push_str(&format!(" {line}\n"));
+    }
+    diff
+}
+
+/// Prints the changelogs to review for the release lines being upgraded through along with notes
+/// on the deprecations that may have been removed along the way.
+fn print_release_notes(current: &Version, target: &Version, releases: &[Version]) {
+    let lines = releases
+        .iter()
+        .filter(|release| release > &current && release <= &target)
+        .map(Version::line)
+        .chain([target.line()])
+        .collect::<BTreeSet<_>>();
+    println!("Review the changelogs for the Pants releases being upgraded through:");
+    for (major, minor) in &lines {
+        println!(
+            "  https://github.com/pantsbuild/pants/blob/main/src/python/pants/notes/\
+            {major}.{minor}.x.md"
+        );
+    }
+    let skipped = lines
+        .iter()
+        .filter(|line| **line != current.line() && **line != target.line())
+        .collect::<Vec<_>>();
+    if !skipped.is_empty() {
+        println!(
+            "Deprecation notes: this upgrade skips over {skipped}. Pants removes deprecated \
+            features in the release line after the one that first warns about them; so \
+            deprecation warnings from the skipped releases were never shown. Consider upgrading \
+            one release line at a time. See: {UPGRADE_TIPS_URL}",
+            skipped = skipped
+                .iter()
+                .map(|(major, minor)| format!("Pants {major}.{minor}.x"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    } else if target.line() != current.line() {
+        println!(
+            "Deprecation notes: fix any deprecation warnings Pants {current} issues before \
+            upgrading since Pants {major}.{minor}.x may remove the deprecated features. See: \
+            {UPGRADE_TIPS_URL}",
+            major = target.line().0,
+            minor = target.line().1
+        );
+    }
+}
+
+/// Upgrades the Pants version configured in `pants.toml`.
+pub(crate) fn upgrade(options: UpgradeOptions) -> Result<()> {
+    let path = pants_config_path(options.config)?;
+    let releases = releases::index(options.releases_file.as_deref())?;
+    let current = current_version(&path, &releases)?;
+    let target = target_version(&options.to, &current, &releases)?;
+    if target == current {
+        println!(
+            "Pants is already at {current} in {path}.",
+            path = path.display()
+        );
+        return Ok(());
+    }
+    if target < current {
+        return Err(anyhow!(
+            "The upgrade target {target} is older than the current Pants version {current}; use \
+            `SCIE_BOOT=set-version scie-pants {target}` to downgrade."
+        ))
+        .error_kind(ErrorKind::BadConfig);
+    }
+
+    let edit = PantsVersionEdit::compute(&path, &target.to_string())?;
+    if options.dry_run {
+        print!(
+            "{diff}",
+            diff = unified_diff(&path, &edit.contents, &edit.edited_contents)
+        );
+    } else {
+        edit.apply()?;
+        println!(
+            "Upgraded the pants_version in {path} from {current} to {target}.",
+            path = path.display()
+        );
+    }
+    print_release_notes(&current, &target, &releases);
+    Ok(())
+}
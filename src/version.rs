@@ -0,0 +1,316 @@
+// Copyright 2023 Pants project contributors.
+// Licensed under the Apache License, Version 2.0 (see LICENSE).
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+/// The PEP 440 version pattern, less the optional leading `v`, which we do not allow.
+///
+/// See: https://peps.python.org/pep-0440/#appendix-b-parsing-version-strings-with-regular-expressions
+const PEP_440_VERSION_PATTERN: &str = r"(?ix)
+    ^
+    (?:(?P<epoch>[0-9]+)!)?
+    (?P<release>[0-9]+(?:\.[0-9]+)*)
+    (?P<suffix>
+        (?:[-_.]?(?P<pre_l>a|b|c|rc|alpha|beta|pre|preview)[-_.]?(?P<pre_n>[0-9]+)?)?
+        (?P<post>-(?P<post_n1>[0-9]+)|[-_.]?(?:post|rev|r)[-_.]?(?P<post_n2>[0-9]+)?)?
+        (?P<dev>[-_.]?dev[-_.]?(?P<dev_n>[0-9]+)?)?
+        (?:\+[a-z0-9]+(?:[-_.][a-z0-9]+)*)?
+    )
+    $
+";
+
+/// Extracts the major and minor components of the release segment of a PEP 440 version string.
+///
+/// See: https://peps.python.org/pep-0440/#public-version-identifiers
+pub(crate) fn major_minor(version: &str) -> Option<(u64, u64)> {
+    let version = version.trim();
+    let version = version
+        .strip_prefix('v')
+        .or_else(|| version.strip_prefix('V'))
+        .unwrap_or(version);
+    let release = match version.split_once('!') {
+        Some((_epoch, rest)) => rest,
+        None => version,
+    };
+    let mut components = release
+        .split(|c: char| !c.is_ascii_digit() && c != '.')
+        .next()?
+        .split('.');
+    let major = components.next()?.parse().ok()?;
+    let minor = components
+        .next()
+        .map_or(Some(0), |minor| minor.parse().ok())?;
+    Some((major, minor))
+}
+
+static PEP_440_VERSION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(PEP_440_VERSION_PATTERN).expect("The PEP 440 pattern is valid."));
+
+fn pep_440_captures(version: &str) -> Option<Captures> {
+    PEP_440_VERSION.captures(version)
+}
+
+/// A shorthand for a Pants version that is resolved against the available Pants releases.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum VersionAlias {
+    /// The newest release, including pre-releases.
+    Latest,
+    /// The newest stable release.
+    LatestStable,
+    /// The newest release in a major.minor line; preferring stable releases.
+    MinorLine(u64, u64),
+}
+
+impl Display for VersionAlias {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionAlias::Latest => write!(f, "latest"),
+            VersionAlias::LatestStable => write!(f, "latest-stable"),
+            VersionAlias::MinorLine(major, minor) => write!(f, "{major}.{minor}"),
+        }
+    }
+}
+
+impl VersionAlias {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "latest" => Some(VersionAlias::Latest),
+            "latest-stable" => Some(VersionAlias::LatestStable),
+            _ => {
+                let (major, minor) = value.split_once('.')?;
+                if [major, minor]
+                    .iter()
+                    .all(|c| !c.is_empty() && c.chars().all(|c| c.is_ascii_digit()))
+                {
+                    Some(VersionAlias::MinorLine(
+                        major.parse().ok()?,
+                        minor.parse().ok()?,
+                    ))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Picks the release this alias refers to from the given releases, if any.
+    pub(crate) fn select<'a>(&self, releases: &'a [Version]) -> Option<&'a Version> {
+        match self {
+            VersionAlias::Latest => releases.iter().max(),
+            VersionAlias::LatestStable => releases.iter().filter(|v| !v.is_prerelease()).max(),
+            VersionAlias::MinorLine(major, minor) => {
+                let line = releases
+                    .iter()
+                    .filter(|v| v.line() == (*major, *minor))
+                    .collect::<Vec<_>>();
+                line.iter()
+                    .filter(|v| !v.is_prerelease())
+                    .max()
+                    .or_else(|| line.iter().max())
+                    .copied()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum PreReleaseKind {
+    Alpha,
+    Beta,
+    ReleaseCandidate,
+}
+
+/// The pre-release sort key; a dev release of a final release sorts before any of its
+/// pre-releases.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum PreRelease {
+    DevOnly,
+    Pre(PreReleaseKind, u64),
+    Final,
+}
+
+/// The dev release sort key; a dev release sorts before the release it leads up to.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum DevRelease {
+    Dev(u64),
+    Release,
+}
+
+/// A PEP 440 version that orders as PEP 440 prescribes, less local version labels.
+#[derive(Clone, Debug)]
+pub(crate) struct Version {
+    raw: String,
+    epoch: u64,
+    release: Vec<u64>,
+    pre: PreRelease,
+    post: Option<u64>,
+    dev: DevRelease,
+}
+
+impl Version {
+    pub(crate) fn parse(version: &str) -> Option<Self> {
+        let captures = pep_440_captures(version)?;
+        let number = |name: &str| -> Option<u64> {
+            captures
+                .name(name)
+                .map_or(Some(0), |number| number.as_str().parse().ok())
+        };
+        let release = captures["release"]
+            .split('.')
+            .map(|component| component.parse().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let dev = match captures.name("dev") {
+            Some(_) => DevRelease::Dev(number("dev_n")?),
+            None => DevRelease::Release,
+        };
+        let pre = match captures
+            .name("pre_l")
+            .map(|l| l.as_str().to_ascii_lowercase())
+        {
+            Some(label) => {
+                let kind = match label.as_str() {
+                    "a" | "alpha" => PreReleaseKind::Alpha,
+                    "b" | "beta" => PreReleaseKind::Beta,
+                    _ => PreReleaseKind::ReleaseCandidate,
+                };
+                PreRelease::Pre(kind, number("pre_n")?)
+            }
+            None if dev != DevRelease::Release => PreRelease::DevOnly,
+            None => PreRelease::Final,
+        };
+        let post = match captures.name("post") {
+            Some(_) if captures.name("post_n1").is_some() => Some(number("post_n1")?),
+            Some(_) => Some(number("post_n2")?),
+            None => None,
+        };
+        Some(Self {
+            raw: version.to_string(),
+            epoch: number("epoch")?,
+            release,
+            pre,
+            post,
+            dev,
+        })
+    }
+
+    fn minor(&self) -> u64 {
+        self.release.get(1).copied().unwrap_or_default()
+    }
+
+    /// The major and minor components of the release; identifying its release line.
+    pub(crate) fn line(&self) -> (u64, u64) {
+        (self.release[0], self.minor())
+    }
+
+    pub(crate) fn is_prerelease(&self) -> bool {
+        self.pre != PreRelease::Final || self.dev != DevRelease::Release
+    }
+
+    fn release_key(&self) -> &[u64] {
+        // N.B.: Trailing zeros are not significant; e.g.: 2.17 == 2.17.0.
+        let significant = self
+            .release
+            .iter()
+            .rposition(|component| *component != 0)
+            .map_or(0, |index| index + 1);
+        &self.release[..significant]
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{raw}", raw = self.raw)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            self.epoch,
+            self.release_key(),
+            self.pre,
+            self.post,
+            self.dev,
+        )
+            .cmp(&(
+                other.epoch,
+                other.release_key(),
+                other.pre,
+                other.post,
+                other.dev,
+            ))
+    }
+}
+
+/// Checks that the given string is a PEP 440 version that could name a Pants release or else a
+/// `VersionAlias`, explaining the problem if not.
+pub(crate) fn validate_pants_version(version: &str) -> Result<(), String> {
+    if VersionAlias::parse(version).is_some() {
+        return Ok(());
+    }
+    if let Some(rest) = version
+        .strip_prefix('v')
+        .or_else(|| version.strip_prefix('V'))
+    {
+        let suggestion = match validate_pants_version(rest) {
+            Ok(()) => format!("; did you mean {rest}?"),
+            Err(_) => "".to_string(),
+        };
+        return Err(format!("leading 'v' not allowed in {version}{suggestion}"));
+    }
+    let Some(captures) = pep_440_captures(version) else {
+        return Err(format!(
+            "{version} is not a valid PEP 440 version (see \
+            https://peps.python.org/pep-0440/#public-version-identifiers)"
+        ));
+    };
+    let release = &captures["release"];
+    let components = release.split('.').count();
+    if components < 3 {
+        let epoch = captures
+            .name("epoch")
+            .map(|epoch| format!("{epoch}!", epoch = epoch.as_str()))
+            .unwrap_or_default();
+        let padding = ".0".repeat(3 - components);
+        let suffix = &captures["suffix"];
+        return Err(format!(
+            "Pants versions have three release components; did you mean \
+            {epoch}{release}{padding}{suffix}?"
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the given string is a full git commit SHA, explaining the problem if not.
+pub(crate) fn validate_pants_sha(sha: &str) -> Result<(), String> {
+    if sha.len() != 40 {
+        return Err(format!(
+            "{sha} is {length} characters long but a full 40 character git commit SHA is required",
+            length = sha.chars().count()
+        ));
+    }
+    if !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "{sha} contains non-hexadecimal characters but a git commit SHA is required"
+        ));
+    }
+    Ok(())
+}